@@ -1,2 +1,4 @@
 
+#[allow(clippy::module_inception)]
 pub mod application;
+pub mod layer_filter;
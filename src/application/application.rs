@@ -9,20 +9,35 @@ use std::error::Error;
 use crate::shape_factory::shape_factory::ShapeFactory;
 use crate::utils::generic_error::GenericError;
 
+use crate::shapes::layered_shape::LayeredShape;
+use crate::utils::layer::Layer;
+
 use crate::shape_visitor::draw_shape_visitor::DrawShapeVisitor;
+use crate::rasterizer::render_mode::RenderMode;
+use crate::application::layer_filter::LayerFilter;
+
+// Layer of the shapes before the first LAYER line
+const DEFAULT_LAYER: &str = "default";
+
+// Shapes in drawing order with every layer of the input file
+type LayeredShapes = (Vec<Box<dyn Shape>>, Vec<Layer>);
 
 pub struct Application {}
 
 impl Application {
-    pub fn run(input_file: &String, output_file: &String) -> Result<(), Box<dyn Error>> {
-        let shapes = read_shapes(&input_file)?;
-        let draw_visitor = draw_shapes(&shapes)?;
+    pub fn run(input_file: &String, output_file: &String, render_mode: RenderMode, layer_filter: &LayerFilter)
+    -> Result<(), Box<dyn Error>> {
+        let (shapes, layers) = read_shapes(input_file)?;
+        layer_filter.check_locked_layers(&layers)?;
+        let draw_visitor = draw_shapes(&shapes, render_mode, layer_filter)?;
         write_image_to_file(output_file, &draw_visitor)?;
         return Ok(());
     }
 }
 
-fn read_shapes(input_file: &String) -> Result<Vec<Box<dyn Shape>>, Box<dyn Error>> {
+// LAYER lines are not counted in the shapes number. Shapes are sorted by the order in which their layers first
+// appear and then by their order in the file, and every shape but the canvas is wrapped with its layer.
+fn read_shapes(input_file: &String) -> Result<LayeredShapes, Box<dyn Error>> {
     let file = open_file(input_file)?;
 
     let buf_reader = BufReader::new(file);
@@ -30,23 +45,58 @@ fn read_shapes(input_file: &String) -> Result<Vec<Box<dyn Shape>>, Box<dyn Error
 
     let shapes_number = read_shapes_number(&mut lines)?;
 
-    let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+    // Shapes are wrapped once every LAYER line is read, since any of them can hide or lock the layer
+    let mut shapes: Vec<(Option<usize>, Box<dyn Shape>)> = Vec::new();
     shapes.reserve_exact(shapes_number);
+    let mut layers: Vec<Layer> = Vec::from([Layer::new(String::from(DEFAULT_LAYER), false, false)]);
+    let mut current_layer_index = 0;
+
+    while shapes.len() < shapes_number {
+        let line_string = read_line_string(read_line(&mut lines))?;
+        if let Some(layer) = ShapeFactory::parse_layer(&line_string)? {
+            current_layer_index = match layers.iter().position(|other| other.get_name() == layer.get_name()) {
+                Some(layer_index) => {
+                    layers[layer_index].merge(&layer);
+                    layer_index
+                },
+                None => {
+                    layers.push(layer);
+                    layers.len() - 1
+                }
+            };
+            continue;
+        }
 
-    for _ in 0..shapes_number {
-        let line = read_line(&mut lines);
-        let shape = read_shape_from_line(line)?;
-        shapes.push(shape);
+        let layer_index = if ShapeFactory::is_canvas(&line_string) { None } else { Some(current_layer_index) };
+        let shape = read_shape_from_line(line_string, &mut lines)?;
+        shapes.push((layer_index, shape));
     }
 
-    return Ok(shapes);
+    shapes.sort_by_key(|(layer_index, _)| *layer_index);
+    let shapes = shapes.into_iter().map(|(layer_index, shape)| match layer_index {
+        None => shape,
+        Some(layer_index) => Box::new(LayeredShape::new(shape, layers[layer_index].clone())) as Box<dyn Shape>
+    }).collect();
+    return Ok((shapes, layers));
 }
 
-fn draw_shapes(shapes: &Vec<Box<dyn Shape>>) -> Result<DrawShapeVisitor, Box<dyn Error>>
-{
-    let mut draw_visitor = DrawShapeVisitor::new();
+// Every layer is drawn as its own scope, so clipping regions and soft masks do not apply beyond their layer
+fn draw_shapes(shapes: &Vec<Box<dyn Shape>>, render_mode: RenderMode, layer_filter: &LayerFilter)
+-> Result<DrawShapeVisitor, Box<dyn Error>> {
+    let mut draw_visitor = DrawShapeVisitor::new(render_mode);
+    let mut current_layer: Option<&String> = None;
 
     for shape in shapes {
+        let layer = shape.get_layer();
+        if layer.is_some_and(|layer| !layer_filter.is_layer_drawn(layer)) {
+            continue;
+        }
+
+        let layer_name = layer.map(|layer| layer.get_name());
+        if layer_name != current_layer {
+            draw_visitor.reset_clip();
+            current_layer = layer_name;
+        }
         shape.accept(&mut draw_visitor)?;
     }
     return Ok(draw_visitor);
@@ -73,7 +123,7 @@ fn open_file(input_file: &String) -> Result<File, Box<dyn Error>> {
 fn read_shapes_number(lines: &mut Lines<BufReader<File>>) -> Result<usize, Box<dyn Error>> {
     return match lines.next() {
         None => {
-            let error_string = format!("Input file does not have at least one line");
+            let error_string = String::from("Input file does not have at least one line");
             let error = GenericError::new(error_string);
             return Err(Box::new(error));
         },
@@ -98,21 +148,55 @@ fn read_shapes_number(lines: &mut Lines<BufReader<File>>) -> Result<usize, Box<d
 fn read_line(lines: &mut Lines<BufReader<File>>) -> Result<String, std::io::Error> {
     return match lines.next() {
         None => {
-            let error_string = format!("No more lines to parse");
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, error_string));
+            let error_string = String::from("No more lines to parse");
+            return Err(std::io::Error::other(error_string));
         },
         Some(some) => some
     };
 }
 
-fn read_shape_from_line(line: Result<String, std::io::Error>) -> Result<Box<dyn Shape>, Box<dyn Error>> {
-    let line_string = match line {
-        Ok(line_str) => line_str,
+// A group or a soft mask is a single shape made of the lines up to its END line, which are not counted
+// in the shapes number
+fn read_shape_from_line(line_string: String, lines: &mut Lines<BufReader<File>>)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    if ShapeFactory::starts_block(&line_string) {
+        let children = read_block_children(&line_string, lines)?;
+        return ShapeFactory::parse_block(&line_string, children);
+    }
+    return ShapeFactory::parse_shape(&line_string);
+}
+
+fn read_block_children(block_line: &String, lines: &mut Lines<BufReader<File>>)
+-> Result<Vec<Box<dyn Shape>>, Box<dyn Error>> {
+    let mut children: Vec<Box<dyn Shape>> = Vec::new();
+    loop {
+        let line_string = match lines.next() {
+            None => {
+                let error_string = format!("{} does not seem to have an END line", block_line);
+                let error = GenericError::new(error_string);
+                return Err(Box::new(error));
+            },
+            Some(line) => read_line_string(line)?
+        };
+
+        if ShapeFactory::ends_block(&line_string) {
+            return Ok(children);
+        } else if ShapeFactory::starts_block(&line_string) {
+            let grandchildren = read_block_children(&line_string, lines)?;
+            children.push(ShapeFactory::parse_block(&line_string, grandchildren)?);
+        } else {
+            children.push(ShapeFactory::parse_block_child(&line_string)?);
+        }
+    }
+}
+
+fn read_line_string(line: Result<String, std::io::Error>) -> Result<String, Box<dyn Error>> {
+    return match line {
+        Ok(line_str) => Ok(line_str),
         Err(err) => {
             let error_string = format!("Error while parsing line: {}", err);
             let error = GenericError::new(error_string);
             return Err(Box::new(error));
         }
     };
-    return ShapeFactory::parse_shape(&line_string);
 }
@@ -0,0 +1,50 @@
+
+use crate::utils::{ generic_error::GenericError, layer::Layer };
+
+use std::vec::Vec;
+use std::string::String;
+use std::error::Error;
+
+// Layers of the input file that are drawn, chosen with command line options. The canvas is always drawn.
+#[derive(Default)]
+pub struct LayerFilter {
+    // Every layer is shown when empty
+    shown_layers: Vec<String>,
+    hidden_layers: Vec<String>
+}
+
+impl LayerFilter {
+    pub fn show_layer(&mut self, layer: String) {
+        self.shown_layers.push(layer);
+    }
+
+    pub fn hide_layer(&mut self, layer: String) {
+        self.hidden_layers.push(layer);
+    }
+
+    // Layers hidden in the input file are drawn only when they are shown explicitly. Locked layers are drawn
+    // unless they are hidden in the input file, whatever the options.
+    pub fn is_layer_drawn(&self, layer: &Layer) -> bool {
+        if layer.is_locked() {
+            return !layer.is_hidden();
+        }
+        if !self.shown_layers.is_empty() && !self.shown_layers.contains(layer.get_name()) {
+            return false;
+        }
+        if self.shown_layers.is_empty() && layer.is_hidden() {
+            return false;
+        }
+        return !self.hidden_layers.contains(layer.get_name());
+    }
+
+    // Options naming a locked layer would be ignored, so they are reported instead
+    pub fn check_locked_layers(&self, layers: &[Layer]) -> Result<(), Box<dyn Error>> {
+        for layer in layers.iter().filter(|layer| layer.is_locked()) {
+            if self.shown_layers.contains(layer.get_name()) || self.hidden_layers.contains(layer.get_name()) {
+                let error_string = format!("Layer {} is locked, its visibility cannot be changed", layer.get_name());
+                return Err(Box::new(GenericError::new(error_string)));
+            }
+        }
+        return Ok(());
+    }
+}
@@ -1,5 +1,9 @@
 
+// Explicit returns and field names are the code style of the whole crate
+#![allow(clippy::needless_return, clippy::redundant_field_names)]
+
 mod application;
+mod rasterizer;
 mod shape_factory;
 mod shape_visitor;
 mod shapes;
@@ -8,19 +12,48 @@ mod utils;
 use std::env;
 use std::vec::Vec;
 use application::application::Application;
+use application::layer_filter::LayerFilter;
+use rasterizer::render_mode::RenderMode;
 
 // http://elf.cs.pub.ro/poo/arhiva/teme/2017/tema2
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    if args.len() != 3 {
-        println!("Usage: {} <input shapes file> <output image path>", args[0]);
+    if args.len() < 3 {
+        print_usage(&args[0]);
         return;
     }
 
+    let mut render_mode = RenderMode::Aliased;
+    let mut layer_filter = LayerFilter::default();
+    let mut options = args[3..].iter();
+    while let Some(option) = options.next() {
+        if option == "--antialias" {
+            render_mode = RenderMode::AntiAliased;
+        } else if option == "--show-layer" || option == "--hide-layer" {
+            let layer = match options.next() {
+                None => {
+                    eprintln!("Missing layer name after {}", option);
+                    print_usage(&args[0]);
+                    return;
+                },
+                Some(layer) => layer.clone()
+            };
+            if option == "--show-layer" {
+                layer_filter.show_layer(layer);
+            } else {
+                layer_filter.hide_layer(layer);
+            }
+        } else {
+            eprintln!("Unknown option: {}", option);
+            print_usage(&args[0]);
+            return;
+        }
+    }
+
     let input_file = &args[1];
     let output_file = &args[2];
-    match Application::run(&input_file, &output_file) {
+    match Application::run(input_file, output_file, render_mode, &layer_filter) {
         Ok(_) => (),
         Err(err) => {
             eprintln!("Error: {}", err);
@@ -28,3 +61,8 @@ fn main() {
         }
     };
 }
+
+fn print_usage(program: &String) {
+    println!("Usage: {} <input shapes file> <output image path> [--antialias] [--show-layer <name>]... \
+              [--hide-layer <name>]...", program);
+}
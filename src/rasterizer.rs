@@ -0,0 +1,12 @@
+
+pub mod scanline;
+pub mod mask;
+pub mod antialiasing;
+pub mod render_mode;
+pub mod flattening;
+pub mod stroke;
+pub mod dash;
+pub mod bitmap_font;
+pub mod text_layout;
+pub mod truetype_font;
+pub mod blur;
@@ -0,0 +1,70 @@
+
+use crate::rasterizer::mask::Mask;
+use crate::utils::point::Point;
+
+// Xiaolin Wu's line algorithm, the two pixels closest to the line share its coverage.
+// The pixels of the end point are left out when include_end is false.
+// https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+pub fn add_line_coverage(mask: &mut Mask, p0: Point, p1: Point, include_end: bool) {
+    let (mut x0, mut y0, mut x1, mut y1) = (p0.x as f64, p0.y as f64, p1.x as f64, p1.y as f64);
+    let is_steep = (y1 - y0).abs() > (x1 - x0).abs();
+
+    if is_steep {
+        std::mem::swap(&mut x0, &mut y0);
+        std::mem::swap(&mut x1, &mut y1);
+    }
+    let excluded_x = if include_end { None } else { Some(x1 as i32) };
+    if x0 > x1 {
+        std::mem::swap(&mut x0, &mut x1);
+        std::mem::swap(&mut y0, &mut y1);
+    }
+
+    let dx = x1 - x0;
+    let gradient = if dx == 0.0 { 0.0 } else { (y1 - y0) / dx };
+
+    let mut y = y0;
+    for x in (x0 as i32)..=(x1 as i32) {
+        let y_floor = y.floor();
+        let fraction = (y - y_floor) as f32;
+
+        if excluded_x != Some(x) {
+            plot(mask, is_steep, x, y_floor as i32, 1.0 - fraction);
+            plot(mask, is_steep, x, y_floor as i32 + 1, fraction);
+        }
+        y += gradient;
+    }
+}
+
+// The outline is a ring one pixel wide centered on the radius and the fill is the disk reaching
+// the outer edge of the ring, so no background shows between the two.
+// Coverages are approximated from the distance between the pixel center and the circle center.
+pub fn add_circle_coverage(outline_mask: &mut Mask, fill_mask: &mut Mask, center: Point, radius: u32) {
+    let extent = radius as i64 + 1;
+    let radius = radius as f64;
+
+    // Only the pixels of the square around the circle that lie inside the masks are visited
+    let x_start = std::cmp::max(center.x as i64 - extent, 0);
+    let x_end = std::cmp::min(center.x as i64 + extent, outline_mask.get_width() as i64 - 1);
+    let y_start = std::cmp::max(center.y as i64 - extent, 0);
+    let y_end = std::cmp::min(center.y as i64 + extent, outline_mask.get_height() as i64 - 1);
+
+    for y in y_start..=y_end {
+        for x in x_start..=x_end {
+            let (dx, dy) = ((x - center.x as i64) as f64, (y - center.y as i64) as f64);
+            let distance = (dx * dx + dy * dy).sqrt();
+            let outline_coverage = 1.0 - (distance - radius).abs();
+            let fill_coverage = radius + 0.5 - distance;
+
+            outline_mask.add_coverage(x as i32, y as i32, outline_coverage.clamp(0.0, 1.0) as f32);
+            fill_mask.add_coverage(x as i32, y as i32, fill_coverage.clamp(0.0, 1.0) as f32);
+        }
+    }
+}
+
+fn plot(mask: &mut Mask, is_steep: bool, x: i32, y: i32, coverage: f32) {
+    if is_steep {
+        mask.add_coverage(y, x, coverage);
+    } else {
+        mask.add_coverage(x, y, coverage);
+    }
+}
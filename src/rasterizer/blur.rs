@@ -0,0 +1,103 @@
+
+use image::{RgbaImage, Rgba};
+use std::vec::Vec;
+
+// Gaussian blur whose standard deviation is half of the radius, like the blur radius of CSS shadows. Colors are
+// blurred premultiplied by their alpha, so that transparent pixels do not darken the edges. Only the pixels
+// around the drawn ones are blurred.
+pub fn blur_image(image: &RgbaImage, radius: u32) -> RgbaImage {
+    let (x_min, y_min, x_max, y_max) = match get_drawn_bounds(image) {
+        None => return image.clone(),
+        Some(bounds) => bounds
+    };
+    if radius == 0 {
+        return image.clone();
+    }
+
+    let kernel = get_gaussian_kernel(radius as f64 / 2.0);
+    let kernel_radius = (kernel.len() / 2) as i64;
+    let x_min = std::cmp::max(x_min as i64 - kernel_radius, 0);
+    let y_min = std::cmp::max(y_min as i64 - kernel_radius, 0);
+    let x_max = std::cmp::min(x_max as i64 + kernel_radius, image.width() as i64 - 1);
+    let y_max = std::cmp::min(y_max as i64 + kernel_radius, image.height() as i64 - 1);
+    let width = (x_max - x_min + 1) as usize;
+    let height = (y_max - y_min + 1) as usize;
+
+    let mut premultiplied = vec![[0.0f32; 4]; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = image.get_pixel((x_min + x as i64) as u32, (y_min + y as i64) as u32);
+            let alpha = pixel[3] as f32 / 255.0;
+            premultiplied[y * width + x] = [pixel[0] as f32 * alpha, pixel[1] as f32 * alpha,
+                                            pixel[2] as f32 * alpha, pixel[3] as f32];
+        }
+    }
+
+    // The kernel is separable: rows are blurred first, then columns
+    let horizontal = convolve(&premultiplied, height, width, &kernel, width, 1);
+    let blurred = convolve(&horizontal, width, height, &kernel, 1, width);
+
+    let mut result = RgbaImage::new(image.width(), image.height());
+    for y in 0..height {
+        for x in 0..width {
+            let [r, g, b, a] = blurred[y * width + x];
+            if a < 0.5 {
+                continue;
+            }
+            let unpremultiply = |channel: f32| (channel * 255.0 / a).round().clamp(0.0, 255.0) as u8;
+            let pixel = Rgba([unpremultiply(r), unpremultiply(g), unpremultiply(b), a.round().min(255.0) as u8]);
+            result.put_pixel((x_min + x as i64) as u32, (y_min + y as i64) as u32, pixel);
+        }
+    }
+    return result;
+}
+
+// Bounds of the pixels that are not fully transparent, inclusive
+fn get_drawn_bounds(image: &RgbaImage) -> Option<(u32, u32, u32, u32)> {
+    let mut bounds: Option<(u32, u32, u32, u32)> = None;
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if pixel[3] == 0 {
+            continue;
+        }
+        bounds = Some(match bounds {
+            None => (x, y, x, y),
+            Some((x_min, y_min, x_max, y_max)) => (x_min.min(x), y_min.min(y), x_max.max(x), y_max.max(y))
+        });
+    }
+    return bounds;
+}
+
+// Weights up to three standard deviations away from the center, summing to 1
+fn get_gaussian_kernel(sigma: f64) -> Vec<f32> {
+    let kernel_radius = (3.0 * sigma).ceil() as i64;
+    let weights: Vec<f64> = (-kernel_radius..=kernel_radius)
+                            .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+                            .collect();
+    let sum: f64 = weights.iter().sum();
+    return weights.iter().map(|weight| (weight / sum) as f32).collect();
+}
+
+// Convolves every line of the buffer with the kernel, a line being `length` pixels `step` apart and lines
+// starting `line_step` pixels apart. Pixels beyond the ends of a line are transparent.
+fn convolve(pixels: &[[f32; 4]], lines: usize, length: usize, kernel: &[f32], line_step: usize, step: usize)
+-> Vec<[f32; 4]> {
+    let kernel_radius = (kernel.len() / 2) as i64;
+    let mut result = vec![[0.0f32; 4]; pixels.len()];
+    for line in 0..lines {
+        for i in 0..length as i64 {
+            let mut sum = [0.0f32; 4];
+            for (k, weight) in kernel.iter().enumerate() {
+                let j = i + k as i64 - kernel_radius;
+                if j < 0 || j >= length as i64 {
+                    continue;
+                }
+                let pixel = pixels[line * line_step + j as usize * step];
+                for channel in 0..4 {
+                    sum[channel] += pixel[channel] * weight;
+                }
+            }
+            result[line * line_step + i as usize * step] = sum;
+        }
+    }
+    return result;
+}
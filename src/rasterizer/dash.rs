@@ -0,0 +1,165 @@
+
+use crate::utils::float_point::FloatPoint;
+
+use std::vec::Vec;
+
+// Shortest total length of a dash pattern, shorter ones would split an outline into too many dashes
+pub const MIN_DASH_PATTERN_LENGTH: f64 = 0.1;
+
+// Most dashes an outline can be split into, long outlines with short patterns would take too much memory
+pub const MAX_DASHES: usize = 100000;
+
+// Splits an outline into the dashes of a dash pattern, each dash being an open polyline.
+// Lengths alternate between dashes and gaps, an odd number of lengths is repeated to get an even
+// one and the offset shifts the start of the pattern along the outline, like SVG's
+// stroke-dasharray and stroke-dashoffset. The pattern continues around corners, so a dash may go
+// through several segments of the outline and zero length dashes are kept as single points. Patterns shorter
+// than MIN_DASH_PATTERN_LENGTH give no dashes and outlines with more than MAX_DASHES dashes give none.
+pub fn get_dashes(points: &[FloatPoint], is_closed: bool, dash_array: &[f64], dash_offset: f64)
+-> Option<Vec<Vec<FloatPoint>>> {
+    let mut pattern = dash_array.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(dash_array);
+    }
+    let pattern_length: f64 = pattern.iter().sum();
+
+    let mut dashes: Vec<Vec<FloatPoint>> = Vec::new();
+    if points.is_empty() || pattern_length < MIN_DASH_PATTERN_LENGTH {
+        return Some(dashes);
+    }
+
+    // A dash ending exactly at the offset is skipped, a zero length one starting there is not
+    let mut pattern_index = 0;
+    let mut remaining = dash_offset.rem_euclid(pattern_length);
+    while remaining > pattern[pattern_index] || (remaining == pattern[pattern_index] && remaining > 0.0) {
+        remaining -= pattern[pattern_index];
+        pattern_index = (pattern_index + 1) % pattern.len();
+    }
+    remaining = pattern[pattern_index] - remaining;
+
+    let mut dash: Vec<FloatPoint> = Vec::new();
+    if pattern_index % 2 == 0 {
+        dash.push(points[0]);
+    }
+
+    let segments_number = if is_closed { points.len() } else { points.len() - 1 };
+    for point_index in 0..segments_number {
+        let mut start = points[point_index];
+        let end = points[(point_index + 1) % points.len()];
+        let mut segment_length = end.subtract(start).length();
+
+        while segment_length > remaining {
+            let split_point = start.add(end.subtract(start).scale(remaining / segment_length));
+            dash.push(split_point);
+            if pattern_index % 2 == 0 {
+                if dashes.len() == MAX_DASHES {
+                    return None;
+                }
+                dashes.push(std::mem::take(&mut dash));
+            }
+
+            segment_length -= remaining;
+            start = split_point;
+            pattern_index = (pattern_index + 1) % pattern.len();
+            remaining = pattern[pattern_index];
+        }
+
+        remaining -= segment_length;
+        if pattern_index % 2 == 0 {
+            dash.push(end);
+        }
+    }
+
+    if !dash.is_empty() {
+        if dashes.len() == MAX_DASHES {
+            return None;
+        }
+        dashes.push(dash);
+    }
+    return Some(dashes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_line(length: f64) -> Vec<FloatPoint> {
+        return Vec::from([FloatPoint::new(0.0, 0.0), FloatPoint::new(length, 0.0)]);
+    }
+
+    fn get_dash_points(dashes: &[Vec<FloatPoint>]) -> Vec<Vec<(f64, f64)>> {
+        return dashes.iter().map(|dash| dash.iter().map(|point| (point.x, point.y)).collect()).collect();
+    }
+
+    #[test]
+    fn alternates_dashes_and_gaps() {
+        let dashes = get_dashes(&get_line(10.0), false, &[2.0, 3.0], 0.0).unwrap();
+        assert_eq!(get_dash_points(&dashes), Vec::from([Vec::from([(0.0, 0.0), (2.0, 0.0)]),
+                                                         Vec::from([(5.0, 0.0), (7.0, 0.0)])]));
+    }
+
+    #[test]
+    fn repeats_odd_patterns() {
+        let dashes = get_dashes(&get_line(10.0), false, &[2.0], 0.0).unwrap();
+        assert_eq!(get_dash_points(&dashes), Vec::from([Vec::from([(0.0, 0.0), (2.0, 0.0)]),
+                                                         Vec::from([(4.0, 0.0), (6.0, 0.0)]),
+                                                         Vec::from([(8.0, 0.0), (10.0, 0.0)])]));
+    }
+
+    #[test]
+    fn shifts_the_pattern_by_the_offset() {
+        let dashes = get_dashes(&get_line(10.0), false, &[2.0, 3.0], 1.0).unwrap();
+        assert_eq!(get_dash_points(&dashes), Vec::from([Vec::from([(0.0, 0.0), (1.0, 0.0)]),
+                                                         Vec::from([(4.0, 0.0), (6.0, 0.0)]),
+                                                         Vec::from([(9.0, 0.0), (10.0, 0.0)])]));
+
+        let dashes = get_dashes(&get_line(10.0), false, &[2.0, 3.0], -1.0).unwrap();
+        assert_eq!(get_dash_points(&dashes), Vec::from([Vec::from([(1.0, 0.0), (3.0, 0.0)]),
+                                                         Vec::from([(6.0, 0.0), (8.0, 0.0)])]));
+    }
+
+    #[test]
+    fn skips_a_dash_ending_at_the_offset() {
+        let dashes = get_dashes(&get_line(10.0), false, &[2.0, 3.0], 2.0).unwrap();
+        assert_eq!(get_dash_points(&dashes), Vec::from([Vec::from([(3.0, 0.0), (5.0, 0.0)]),
+                                                         Vec::from([(8.0, 0.0), (10.0, 0.0)])]));
+    }
+
+    #[test]
+    fn continues_dashes_around_corners() {
+        let points = [FloatPoint::new(0.0, 0.0), FloatPoint::new(4.0, 0.0), FloatPoint::new(4.0, 4.0)];
+        let dashes = get_dashes(&points, false, &[6.0, 1.0], 0.0).unwrap();
+        assert_eq!(get_dash_points(&dashes), Vec::from([Vec::from([(0.0, 0.0), (4.0, 0.0), (4.0, 2.0)]),
+                                                         Vec::from([(4.0, 3.0), (4.0, 4.0)])]));
+    }
+
+    #[test]
+    fn dashes_closed_outlines_up_to_their_start() {
+        let points = [FloatPoint::new(0.0, 0.0), FloatPoint::new(4.0, 0.0), FloatPoint::new(4.0, 4.0)];
+        let open_dashes = get_dashes(&points, false, &[20.0, 1.0], 0.0).unwrap();
+        let closed_dashes = get_dashes(&points, true, &[20.0, 1.0], 0.0).unwrap();
+        assert_eq!(get_dash_points(&open_dashes)[0].last(), Some(&(4.0, 4.0)));
+        assert_eq!(get_dash_points(&closed_dashes)[0].last(), Some(&(0.0, 0.0)));
+    }
+
+    #[test]
+    fn keeps_zero_length_dashes_as_points() {
+        let dashes = get_dashes(&get_line(7.0), false, &[0.0, 3.0], 0.0).unwrap();
+        assert_eq!(get_dash_points(&dashes), Vec::from([Vec::from([(0.0, 0.0), (0.0, 0.0)]),
+                                                         Vec::from([(3.0, 0.0), (3.0, 0.0)]),
+                                                         Vec::from([(6.0, 0.0), (6.0, 0.0)])]));
+    }
+
+    #[test]
+    fn gives_no_dashes_for_too_short_patterns() {
+        assert!(get_dashes(&get_line(10.0), false, &[0.05, 0.0], 0.0).unwrap().is_empty());
+        assert!(get_dashes(&[], false, &[2.0, 3.0], 0.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn gives_nothing_for_too_many_dashes() {
+        let length = MAX_DASHES as f64;
+        assert_eq!(get_dashes(&get_line(length * 2.0 - 1.0), false, &[1.0, 1.0], 0.0).unwrap().len(), MAX_DASHES);
+        assert!(get_dashes(&get_line(length * 2.0 + 1.0), false, &[1.0, 1.0], 0.0).is_none());
+    }
+}
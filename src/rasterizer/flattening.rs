@@ -0,0 +1,202 @@
+
+use crate::utils::{ float_point::FloatPoint, path_command::PathCommand, corner_radii::CornerRadii };
+
+use std::f64::consts::PI;
+use std::vec::Vec;
+
+// Maximum distance in pixels between a curve and the segments approximating it. Curves that are transformed
+// afterwards are flattened with a tolerance divided by the scale factor of the transform.
+pub const TOLERANCE: f64 = 0.1;
+
+// Bounds the number of points of an arc flattened with a tiny tolerance
+const MAX_ARC_SEGMENTS: usize = 65536;
+
+// Maximum number of times a Bézier curve is split in halves, bounds the work for degenerate curves
+const MAX_SUBDIVISION_DEPTH: u32 = 16;
+
+// Approximates a circle with a polygon whose vertices are counterclockwise on screen
+pub fn get_circle_points(center: FloatPoint, radius: f64, tolerance: f64) -> Vec<FloatPoint> {
+    let segments_number = get_arc_segments_number(radius, 2.0 * PI, tolerance);
+    let mut points: Vec<FloatPoint> = Vec::with_capacity(segments_number);
+
+    for segment_index in 0..segments_number {
+        let angle = -2.0 * PI * (segment_index as f64) / (segments_number as f64);
+        points.push(FloatPoint::new(center.x + radius * angle.cos(), center.y + radius * angle.sin()));
+    }
+    return points;
+}
+
+// Vertices of a polygon inscribed in a circle, clockwise on screen. Vertex radii alternate through the
+// given list, one radius gives a regular polygon and two give a star. The first vertex is at the top of
+// the circle before the clockwise rotation.
+pub fn get_inscribed_polygon_points(center: FloatPoint, radii: &[f64], vertices_number: u32, rotation: f64)
+-> Vec<FloatPoint> {
+    let mut points: Vec<FloatPoint> = Vec::with_capacity(vertices_number as usize);
+
+    for vertex_index in 0..vertices_number {
+        let radius = radii[(vertex_index as usize) % radii.len()];
+        let angle = rotation - 0.5 * PI + 2.0 * PI * (vertex_index as f64) / (vertices_number as f64);
+        points.push(FloatPoint::new(center.x + radius * angle.cos(), center.y + radius * angle.sin()));
+    }
+    return points;
+}
+
+// Approximates an arc of an ellipse rotated around its center with a polyline, both end points
+// included. Angles are in radians and grow clockwise on screen, starting from the horizontal axis of
+// the ellipse, a negative sweep angle goes counterclockwise. Sweeps beyond a full turn are drawn as a full turn.
+pub fn get_elliptical_arc_points(center: FloatPoint, horizontal_radius: f64, vertical_radius: f64,
+                                 rotation: f64, start_angle: f64, sweep_angle: f64, tolerance: f64)
+-> Vec<FloatPoint> {
+    let sweep_angle = sweep_angle.clamp(-2.0 * PI, 2.0 * PI);
+    let radius = f64::max(horizontal_radius, vertical_radius);
+    let segments_number = get_arc_segments_number(radius, sweep_angle, tolerance);
+    let mut points: Vec<FloatPoint> = Vec::with_capacity(segments_number + 1);
+
+    for segment_index in 0..=segments_number {
+        let angle = start_angle + sweep_angle * (segment_index as f64) / (segments_number as f64);
+        let x = horizontal_radius * angle.cos();
+        let y = vertical_radius * angle.sin();
+        points.push(FloatPoint::new(center.x + x * rotation.cos() - y * rotation.sin(),
+                                    center.y + x * rotation.sin() + y * rotation.cos()));
+    }
+    return points;
+}
+
+// Whole ellipse as a closed polygon, the vertices are clockwise on screen
+pub fn get_ellipse_points(center: FloatPoint, horizontal_radius: f64, vertical_radius: f64, rotation: f64,
+                          tolerance: f64) -> Vec<FloatPoint> {
+    let mut points = get_elliptical_arc_points(center, horizontal_radius, vertical_radius, rotation, 0.0, 2.0 * PI,
+                                               tolerance);
+    points.pop();
+    return points;
+}
+
+// Rectangle with rounded corners as a closed polygon, the vertices are clockwise on screen. Radii are
+// scaled down together when adjacent corners do not fit along a side, like in CSS.
+pub fn get_rounded_rectangle_points(top_left: FloatPoint, bottom_right: FloatPoint, corner_radii: &CornerRadii,
+                                    tolerance: f64) -> Vec<FloatPoint> {
+    let width = bottom_right.x - top_left.x;
+    let height = bottom_right.y - top_left.y;
+    let top_left_radius = corner_radii.top_left as f64;
+    let top_right_radius = corner_radii.top_right as f64;
+    let bottom_right_radius = corner_radii.bottom_right as f64;
+    let bottom_left_radius = corner_radii.bottom_left as f64;
+
+    let mut factor: f64 = 1.0;
+    for (side, radii_sum) in [(width, top_left_radius + top_right_radius),
+                              (height, top_right_radius + bottom_right_radius),
+                              (width, bottom_right_radius + bottom_left_radius),
+                              (height, bottom_left_radius + top_left_radius)] {
+        if radii_sum > side {
+            factor = f64::min(factor, side / radii_sum);
+        }
+    }
+
+    let corners = [(FloatPoint::new(top_left.x, top_left.y), top_left_radius, PI),
+                   (FloatPoint::new(bottom_right.x, top_left.y), top_right_radius, 1.5 * PI),
+                   (FloatPoint::new(bottom_right.x, bottom_right.y), bottom_right_radius, 0.0),
+                   (FloatPoint::new(top_left.x, bottom_right.y), bottom_left_radius, 0.5 * PI)];
+    let mut points: Vec<FloatPoint> = Vec::new();
+
+    for (corner, radius, start_angle) in corners {
+        let radius = radius * factor;
+        if radius <= 0.0 {
+            points.push(corner);
+            continue;
+        }
+
+        // The center of the corner is inside the rectangle on both axes
+        let center = FloatPoint::new(if corner.x == top_left.x { corner.x + radius } else { corner.x - radius },
+                                     if corner.y == top_left.y { corner.y + radius } else { corner.y - radius });
+        points.extend(get_elliptical_arc_points(center, radius, radius, 0.0, start_angle, 0.5 * PI, tolerance));
+    }
+    return points;
+}
+
+// Approximates every subpath of a path with a polyline, paired with whether the subpath was closed.
+// Subpaths made of a single move-to draw nothing and are left out.
+pub fn get_path_polylines(commands: &[PathCommand], tolerance: f64) -> Vec<(Vec<FloatPoint>, bool)> {
+    let mut polylines: Vec<(Vec<FloatPoint>, bool)> = Vec::new();
+    let mut points: Vec<FloatPoint> = Vec::new();
+
+    for command in commands {
+        match *command {
+            PathCommand::MoveTo(point) => {
+                if points.len() > 1 {
+                    polylines.push((points, false));
+                }
+                points = Vec::from([point]);
+            },
+            PathCommand::LineTo(point) => points.push(point),
+            PathCommand::QuadraticTo(control, end) => {
+                // Degree elevation, every quadratic curve is also a cubic one
+                let start = points.last().copied().unwrap_or(control);
+                let control0 = start.add(control.subtract(start).scale(2.0 / 3.0));
+                let control1 = end.add(control.subtract(end).scale(2.0 / 3.0));
+                add_cubic_bezier_points(&mut points, start, control0, control1, end, tolerance, 0);
+            },
+            PathCommand::CubicTo(control0, control1, end) => {
+                let start = points.last().copied().unwrap_or(control0);
+                add_cubic_bezier_points(&mut points, start, control0, control1, end, tolerance, 0);
+            },
+            PathCommand::Close => {
+                if let Some(start) = points.first().copied() {
+                    polylines.push((points, true));
+                    points = Vec::from([start]);
+                }
+            }
+        }
+    }
+    if points.len() > 1 {
+        polylines.push((points, false));
+    }
+    return polylines;
+}
+
+// Adds the points after the start of a cubic Bézier curve, splitting it in halves (de Casteljau) until
+// every piece is close enough to a segment
+fn add_cubic_bezier_points(points: &mut Vec<FloatPoint>, p0: FloatPoint, p1: FloatPoint, p2: FloatPoint,
+                           p3: FloatPoint, tolerance: f64, depth: u32) {
+    if depth >= MAX_SUBDIVISION_DEPTH || is_cubic_bezier_flat(p0, p1, p2, p3, tolerance) {
+        points.push(p3);
+        return;
+    }
+
+    let p01 = p0.add(p1).scale(0.5);
+    let p12 = p1.add(p2).scale(0.5);
+    let p23 = p2.add(p3).scale(0.5);
+    let p012 = p01.add(p12).scale(0.5);
+    let p123 = p12.add(p23).scale(0.5);
+    let middle = p012.add(p123).scale(0.5);
+
+    add_cubic_bezier_points(points, p0, p01, p012, middle, tolerance, depth + 1);
+    add_cubic_bezier_points(points, middle, p123, p23, p3, tolerance, depth + 1);
+}
+
+// The curve stays inside the convex hull of its control points, so it is flat enough when both
+// control points are close to the segment between its end points
+fn is_cubic_bezier_flat(p0: FloatPoint, p1: FloatPoint, p2: FloatPoint, p3: FloatPoint, tolerance: f64) -> bool {
+    return get_distance_to_segment(p1, p0, p3) <= tolerance && get_distance_to_segment(p2, p0, p3) <= tolerance;
+}
+
+fn get_distance_to_segment(point: FloatPoint, start: FloatPoint, end: FloatPoint) -> f64 {
+    let direction = end.subtract(start);
+    let length_squared = direction.x * direction.x + direction.y * direction.y;
+    if length_squared == 0.0 {
+        return point.subtract(start).length();
+    }
+
+    let offset = point.subtract(start);
+    let t = ((offset.x * direction.x + offset.y * direction.y) / length_squared).clamp(0.0, 1.0);
+    return point.subtract(start.add(direction.scale(t))).length();
+}
+
+// Number of segments needed so that the sagitta of every segment stays below the tolerance
+// https://en.wikipedia.org/wiki/Sagitta_(geometry)
+fn get_arc_segments_number(radius: f64, sweep_angle: f64, tolerance: f64) -> usize {
+    if radius <= tolerance {
+        return 4;
+    }
+    let angle_per_segment = 2.0 * (1.0 - tolerance / radius).acos();
+    return ((sweep_angle.abs() / angle_per_segment).ceil() as usize).clamp(4, MAX_ARC_SEGMENTS);
+}
@@ -0,0 +1,114 @@
+
+use crate::rasterizer::scanline::Span;
+
+use std::vec::Vec;
+
+// Per-shape coverage of every image pixel, between 0 (not covered) and 1 (fully covered).
+// Shapes are rasterized into masks first and composited afterwards, so the pixels a shape
+// touches depend only on its geometry and every pixel is composited at most once.
+pub struct Mask {
+    width: u32,
+    height: u32,
+    coverage: Vec<f32>,
+    x_min: i32,
+    y_min: i32,
+    x_max: i32,
+    y_max: i32
+}
+
+impl Mask {
+    pub fn new(width: u32, height: u32) -> Self {
+        return Mask{width: width, height: height,
+                    coverage: vec![0.0; (width as usize) * (height as usize)],
+                    x_min: width as i32, y_min: height as i32, x_max: -1, y_max: -1};
+    }
+
+    pub fn get_width(&self) -> u32 {
+        return self.width;
+    }
+
+    pub fn get_height(&self) -> u32 {
+        return self.height;
+    }
+
+    pub fn is_inside(&self, x: i32, y: i32) -> bool {
+        return (x >= 0) && ((x as u32) < self.width) && (y >= 0) && ((y as u32) < self.height);
+    }
+
+    pub fn get_coverage(&self, x: i32, y: i32) -> f32 {
+        if !self.is_inside(x, y) {
+            return 0.0;
+        }
+        return self.coverage[self.get_index(x, y)];
+    }
+
+    // Overlapping parts of a shape do not add up, the highest coverage is kept
+    pub fn add_coverage(&mut self, x: i32, y: i32, coverage: f32) {
+        if !self.is_inside(x, y) || coverage <= 0.0 {
+            return;
+        }
+
+        let index = self.get_index(x, y);
+        self.coverage[index] = f32::max(self.coverage[index], f32::min(coverage, 1.0));
+
+        self.x_min = std::cmp::min(self.x_min, x);
+        self.y_min = std::cmp::min(self.y_min, y);
+        self.x_max = std::cmp::max(self.x_max, x);
+        self.y_max = std::cmp::max(self.y_max, y);
+    }
+
+    // Adds up partial coverages of disjoint pieces of a shape, e.g. sub-scanlines of a polygon
+    pub fn accumulate_coverage(&mut self, x: i32, y: i32, coverage: f32) {
+        let current_coverage = self.get_coverage(x, y);
+        self.add_coverage(x, y, current_coverage + coverage);
+    }
+
+    pub fn add_span(&mut self, span: &Span) {
+        for x in span.x_start..=span.x_end {
+            self.add_coverage(x, span.y, 1.0);
+        }
+    }
+
+    // Adds the parts covered by the other mask, e.g. the outlines of several subpaths of a shape
+    pub fn add_mask(&mut self, other: &Mask) {
+        if let Some((x_min, y_min, x_max, y_max)) = other.get_bounds() {
+            for y in y_min..=y_max {
+                for x in x_min..=x_max {
+                    self.add_coverage(x, y, other.get_coverage(x, y));
+                }
+            }
+        }
+    }
+
+    // Removes the parts covered by the other mask, e.g. the outline from the fill of a shape
+    pub fn subtract(&mut self, other: &Mask) {
+        for y in self.y_min..=self.y_max {
+            for x in self.x_min..=self.x_max {
+                let index = self.get_index(x, y);
+                self.coverage[index] *= 1.0 - other.get_coverage(x, y);
+            }
+        }
+    }
+
+    // Keeps the parts also covered by the other mask, e.g. where nested clipping regions overlap
+    pub fn intersect(&mut self, other: &Mask) {
+        for y in self.y_min..=self.y_max {
+            for x in self.x_min..=self.x_max {
+                let index = self.get_index(x, y);
+                self.coverage[index] *= other.get_coverage(x, y);
+            }
+        }
+    }
+
+    // Inclusive bounding box of the covered pixels as (x_min, y_min, x_max, y_max)
+    pub fn get_bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        if self.x_min > self.x_max || self.y_min > self.y_max {
+            return None;
+        }
+        return Some((self.x_min, self.y_min, self.x_max, self.y_max));
+    }
+
+    fn get_index(&self, x: i32, y: i32) -> usize {
+        return (y as usize) * (self.width as usize) + (x as usize);
+    }
+}
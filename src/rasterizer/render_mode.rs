@@ -0,0 +1,8 @@
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum RenderMode {
+    // Every pixel is either fully covered by a shape or not covered at all
+    Aliased,
+    // Edges partially covering a pixel are blended with what is beneath them
+    AntiAliased
+}
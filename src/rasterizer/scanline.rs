@@ -0,0 +1,262 @@
+
+use crate::utils::{float_point::FloatPoint, fill_rule::FillRule};
+use crate::rasterizer::mask::Mask;
+
+use std::vec::Vec;
+
+// Horizontal run of pixels inside a polygon, both ends included
+#[derive(Copy, Clone)]
+pub struct Span {
+    pub y: i32,
+    pub x_start: i32,
+    pub x_end: i32
+}
+
+impl Span {
+    pub fn new(y: i32, x_start: i32, x_end: i32) -> Self {
+        return Span{y: y, x_start: x_start, x_end: x_end};
+    }
+}
+
+struct Edge {
+    y_min: f64,
+    y_max: f64,
+    x_at_y_min: f64,
+    inverse_slope: f64,
+    // +1 if the edge goes downwards, -1 if it goes upwards
+    winding: i32
+}
+
+impl Edge {
+    fn new(p0: FloatPoint, p1: FloatPoint) -> Option<Self> {
+        if p0.y == p1.y {
+            return None;
+        }
+
+        let (top, bottom, winding) = if p0.y < p1.y { (p0, p1, 1) } else { (p1, p0, -1) };
+        let inverse_slope = (bottom.x - top.x) / (bottom.y - top.y);
+        return Some(Edge{y_min: top.y, y_max: bottom.y,
+                         x_at_y_min: top.x, inverse_slope: inverse_slope,
+                         winding: winding});
+    }
+
+    fn get_x_at(&self, y: f64) -> f64 {
+        return self.x_at_y_min + (y - self.y_min) * self.inverse_slope;
+    }
+}
+
+// Edge table scanline polygon filling.
+// Pixel centers lie on integer coordinates and a pixel is filled if its center is inside the polygon.
+// Edges are treated as half-open intervals [y_min, y_max) so vertices shared by two edges are not
+// counted twice. Spans are clipped to a width x height image.
+// A polygon may have several contours (e.g. a ring has an outer and an inner one) and the fill rule
+// decides which parts of self-intersecting or overlapping contours are inside.
+// https://www.cs.rit.edu/~icss571/filling/
+// https://en.wikipedia.org/wiki/Nonzero-rule
+pub fn get_polygon_spans(contours: &[Vec<FloatPoint>], fill_rule: FillRule, width: u32, height: u32) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::new();
+    if width == 0 || height == 0 {
+        return spans;
+    }
+
+    let scanlines = (0..height as i32).map(|y| y as f64);
+    scan_polygon(contours, fill_rule, scanlines, |scanline, x_left, x_right| {
+        let x_start = std::cmp::max(x_left.ceil() as i32, 0);
+        let x_end = std::cmp::min(x_right.ceil() as i32 - 1, width as i32 - 1);
+
+        if x_start <= x_end {
+            spans.push(Span::new(scanline as i32, x_start, x_end));
+        }
+    });
+    return spans;
+}
+
+// Anti-aliased variant of the polygon filling: every pixel row is sampled by several sub-scanlines
+// and the horizontal coverage of every pixel is computed exactly on each of them
+pub fn add_polygon_coverage(contours: &[Vec<FloatPoint>], fill_rule: FillRule, mask: &mut Mask) {
+    let height = mask.get_height() as i32;
+    let sub_scanlines = (0..height * SUB_SCANLINES).map(|index| {
+        return -0.5 + (index as f64 + 0.5) / SUB_SCANLINES as f64;
+    });
+
+    scan_polygon(contours, fill_rule, sub_scanlines, |scanline, x_left, x_right| {
+        let y = scanline.round() as i32;
+        let x_start = (x_left + 0.5).floor() as i32;
+        let x_end = (x_right + 0.5).ceil() as i32 - 1;
+
+        for x in std::cmp::max(x_start, 0)..=std::cmp::min(x_end, mask.get_width() as i32 - 1) {
+            let pixel_left = f64::max(x_left, x as f64 - 0.5);
+            let pixel_right = f64::min(x_right, x as f64 + 0.5);
+            let coverage = (pixel_right - pixel_left) / SUB_SCANLINES as f64;
+            mask.accumulate_coverage(x, y, coverage as f32);
+        }
+    });
+}
+
+const SUB_SCANLINES: i32 = 16;
+
+// Calls add_interval for every interval [x_left, x_right) of every scanline that lies inside the
+// polygon. Scanlines must be given in increasing order.
+fn scan_polygon<I, F>(contours: &[Vec<FloatPoint>], fill_rule: FillRule, scanlines: I, mut add_interval: F)
+where I: Iterator<Item = f64>, F: FnMut(f64, f64, f64) {
+    let mut edge_table: Vec<Edge> = Vec::new();
+    for points in contours {
+        for point_index in 0..points.len() {
+            let p0 = points[point_index];
+            let p1 = points[(point_index + 1) % points.len()];
+            if let Some(edge) = Edge::new(p0, p1) {
+                edge_table.push(edge);
+            }
+        }
+    }
+    if edge_table.is_empty() {
+        return;
+    }
+    edge_table.sort_by(|e0, e1| e0.y_min.total_cmp(&e1.y_min));
+
+    let polygon_y_min = edge_table[0].y_min;
+    let polygon_y_max = edge_table.iter().map(|edge| edge.y_max).fold(f64::MIN, f64::max);
+
+    let mut active_edges: Vec<&Edge> = Vec::new();
+    let mut next_edge = 0;
+    let mut intersections: Vec<(f64, i32)> = Vec::new();
+
+    for scanline in scanlines {
+        if scanline < polygon_y_min {
+            continue;
+        }
+        if scanline >= polygon_y_max {
+            break;
+        }
+
+        while next_edge < edge_table.len() && edge_table[next_edge].y_min <= scanline {
+            active_edges.push(&edge_table[next_edge]);
+            next_edge += 1;
+        }
+        active_edges.retain(|edge| edge.y_max > scanline);
+
+        intersections.clear();
+        for edge in &active_edges {
+            intersections.push((edge.get_x_at(scanline), edge.winding));
+        }
+        intersections.sort_by(|i0, i1| i0.0.total_cmp(&i1.0));
+
+        let mut winding_number = 0;
+        let mut span_start = 0.0;
+        for (x, winding) in &intersections {
+            let was_inside = is_inside(winding_number, fill_rule);
+            winding_number += winding;
+            let is_now_inside = is_inside(winding_number, fill_rule);
+
+            if !was_inside && is_now_inside {
+                span_start = *x;
+            } else if was_inside && !is_now_inside {
+                add_interval(scanline, span_start, *x);
+            }
+        }
+    }
+}
+
+fn is_inside(winding_number: i32, fill_rule: FillRule) -> bool {
+    return match fill_rule {
+        FillRule::EvenOdd => winding_number % 2 != 0,
+        FillRule::NonZero => winding_number != 0
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_square(x: f64, y: f64, size: f64) -> Vec<FloatPoint> {
+        return Vec::from([FloatPoint::new(x, y), FloatPoint::new(x + size, y),
+                          FloatPoint::new(x + size, y + size), FloatPoint::new(x, y + size)]);
+    }
+
+    fn get_span_bounds(spans: &[Span]) -> Vec<(i32, i32, i32)> {
+        return spans.iter().map(|span| (span.y, span.x_start, span.x_end)).collect();
+    }
+
+    #[test]
+    fn fills_pixels_whose_centers_are_inside() {
+        let spans = get_polygon_spans(&[get_square(1.0, 1.0, 3.0)], FillRule::EvenOdd, 10, 10);
+        assert_eq!(get_span_bounds(&spans), Vec::from([(1, 1, 3), (2, 1, 3), (3, 1, 3)]));
+    }
+
+    #[test]
+    fn fills_half_pixel_edges_once() {
+        let spans = get_polygon_spans(&[get_square(0.5, 0.5, 2.0)], FillRule::EvenOdd, 10, 10);
+        assert_eq!(get_span_bounds(&spans), Vec::from([(1, 1, 2), (2, 1, 2)]));
+    }
+
+    #[test]
+    fn clips_spans_to_the_image() {
+        let spans = get_polygon_spans(&[get_square(-5.0, -5.0, 10.0)], FillRule::EvenOdd, 3, 2);
+        assert_eq!(get_span_bounds(&spans), Vec::from([(0, 0, 2), (1, 0, 2)]));
+        assert!(get_polygon_spans(&[get_square(20.0, 20.0, 5.0)], FillRule::EvenOdd, 10, 10).is_empty());
+        assert!(get_polygon_spans(&[get_square(0.0, 0.0, 5.0)], FillRule::EvenOdd, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn fills_nothing_for_flat_polygons() {
+        let line = Vec::from([FloatPoint::new(1.0, 1.0), FloatPoint::new(8.0, 1.0)]);
+        assert!(get_polygon_spans(&[line], FillRule::EvenOdd, 10, 10).is_empty());
+        assert!(get_polygon_spans(&[], FillRule::EvenOdd, 10, 10).is_empty());
+    }
+
+    #[test]
+    fn fills_triangles_between_their_edges() {
+        let triangle = Vec::from([FloatPoint::new(0.0, 0.0), FloatPoint::new(8.0, 0.0), FloatPoint::new(0.0, 8.0)]);
+        let spans = get_polygon_spans(&[triangle], FillRule::EvenOdd, 10, 10);
+        assert_eq!(spans.len(), 8);
+        for span in spans {
+            assert_eq!(span.x_start, 0);
+            assert_eq!(span.x_end, 7 - span.y);
+        }
+    }
+
+    fn is_pixel_filled(spans: &[Span], x: i32, y: i32) -> bool {
+        return spans.iter().any(|span| span.y == y && span.x_start <= x && x <= span.x_end);
+    }
+
+    #[test]
+    fn fill_rules_differ_for_contours_with_the_same_direction() {
+        let contours = [get_square(0.0, 0.0, 9.0), get_square(3.0, 3.0, 3.0)];
+        let even_odd_spans = get_polygon_spans(&contours, FillRule::EvenOdd, 10, 10);
+        let non_zero_spans = get_polygon_spans(&contours, FillRule::NonZero, 10, 10);
+
+        assert!(is_pixel_filled(&even_odd_spans, 1, 4) && is_pixel_filled(&non_zero_spans, 1, 4));
+        assert!(!is_pixel_filled(&even_odd_spans, 4, 4));
+        assert!(is_pixel_filled(&non_zero_spans, 4, 4));
+    }
+
+    #[test]
+    fn fill_rules_agree_for_contours_with_opposite_directions() {
+        let mut hole = get_square(3.0, 3.0, 3.0);
+        hole.reverse();
+        let contours = [get_square(0.0, 0.0, 9.0), hole];
+
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            let spans = get_polygon_spans(&contours, fill_rule, 10, 10);
+            assert!(is_pixel_filled(&spans, 1, 4));
+            assert!(!is_pixel_filled(&spans, 4, 4));
+            assert!(is_pixel_filled(&spans, 7, 4));
+        }
+    }
+
+    #[test]
+    fn fill_rules_differ_for_self_intersecting_polygons() {
+        // Pentagram whose center is wound around twice
+        let star = (0..5).map(|index| {
+            let angle = (index * 144) as f64 * std::f64::consts::PI / 180.0;
+            return FloatPoint::new(10.0 + 9.0 * angle.sin(), 10.0 - 9.0 * angle.cos());
+        }).collect::<Vec<FloatPoint>>();
+        let contours = [star];
+        let even_odd_spans = get_polygon_spans(&contours, FillRule::EvenOdd, 20, 20);
+        let non_zero_spans = get_polygon_spans(&contours, FillRule::NonZero, 20, 20);
+
+        assert!(!is_pixel_filled(&even_odd_spans, 10, 10));
+        assert!(is_pixel_filled(&non_zero_spans, 10, 10));
+        assert!(is_pixel_filled(&even_odd_spans, 10, 3) && is_pixel_filled(&non_zero_spans, 10, 3));
+    }
+}
@@ -0,0 +1,276 @@
+
+use crate::utils::{float_point::FloatPoint, stroke::{Stroke, LineCap, LineJoin}};
+use crate::rasterizer::flattening::get_circle_points;
+
+use std::vec::Vec;
+
+// Turns the outline of a shape into polygon contours that cover the stroke and have to be filled
+// using the non-zero rule. Every segment becomes a rectangle as wide as the stroke and caps and
+// joins are added as separate pieces. All pieces have the same orientation so that overlapping
+// ones add up instead of cancelling out.
+pub fn get_polyline_stroke_contours(points: &[FloatPoint], is_closed: bool, stroke: &Stroke, tolerance: f64)
+-> Vec<Vec<FloatPoint>> {
+    let half_width = stroke.get_width() as f64 / 2.0;
+    let points = remove_repeated_points(points, is_closed);
+    let mut contours: Vec<Vec<FloatPoint>> = Vec::new();
+
+    if points.is_empty() {
+        return contours;
+    }
+    if points.len() == 1 {
+        add_cap(&mut contours, points[0], FloatPoint::new(1.0, 0.0), half_width, stroke.get_cap(), tolerance);
+        add_cap(&mut contours, points[0], FloatPoint::new(-1.0, 0.0), half_width, stroke.get_cap(), tolerance);
+        return contours;
+    }
+
+    let segments_number = if is_closed { points.len() } else { points.len() - 1 };
+    for point_index in 0..segments_number {
+        let p0 = points[point_index];
+        let p1 = points[(point_index + 1) % points.len()];
+        let offset = p1.subtract(p0).normal().unwrap().scale(half_width);
+
+        add_contour(&mut contours, Vec::from([p0.add(offset), p1.add(offset), p1.subtract(offset), p0.subtract(offset)]));
+    }
+
+    let joins_range = if is_closed { 0..points.len() } else { 1..points.len() - 1 };
+    for point_index in joins_range {
+        let previous = points[(point_index + points.len() - 1) % points.len()];
+        let current = points[point_index];
+        let next = points[(point_index + 1) % points.len()];
+        add_join(&mut contours, previous, current, next, half_width, stroke, tolerance);
+    }
+
+    if !is_closed {
+        let last = points.len() - 1;
+        add_cap(&mut contours, points[0], points[0].subtract(points[1]), half_width, stroke.get_cap(), tolerance);
+        add_cap(&mut contours, points[last], points[last].subtract(points[last - 1]), half_width, stroke.get_cap(),
+                tolerance);
+    }
+    return contours;
+}
+
+// Ring between two circles, the inner contour runs in the opposite direction of the outer one
+pub fn get_circle_stroke_contours(center: FloatPoint, radius: f64, stroke: &Stroke, tolerance: f64)
+-> Vec<Vec<FloatPoint>> {
+    let half_width = stroke.get_width() as f64 / 2.0;
+    let mut contours = Vec::from([get_circle_points(center, radius + half_width, tolerance)]);
+
+    if radius > half_width {
+        let mut inner_points = get_circle_points(center, radius - half_width, tolerance);
+        inner_points.reverse();
+        contours.push(inner_points);
+    }
+    return contours;
+}
+
+// Zero length segments have no direction, so they can not have caps or joins
+fn remove_repeated_points(points: &[FloatPoint], is_closed: bool) -> Vec<FloatPoint> {
+    let mut unique_points: Vec<FloatPoint> = Vec::with_capacity(points.len());
+
+    for point in points {
+        if unique_points.last().is_none_or(|last| last.subtract(*point).length() > 0.0) {
+            unique_points.push(*point);
+        }
+    }
+    if is_closed && unique_points.len() > 1
+       && unique_points[0].subtract(*unique_points.last().unwrap()).length() == 0.0 {
+        unique_points.pop();
+    }
+    return unique_points;
+}
+
+// Cap at the end point of a segment, direction points away from the segment
+fn add_cap(contours: &mut Vec<Vec<FloatPoint>>, end: FloatPoint, direction: FloatPoint, half_width: f64,
+           cap: LineCap, tolerance: f64) {
+    match cap {
+        LineCap::Butt => (),
+        LineCap::Round => add_contour(contours, get_circle_points(end, half_width, tolerance)),
+        LineCap::Square => {
+            let offset = direction.normal().unwrap().scale(half_width);
+            let extension = end.add(direction.scale(half_width / direction.length()));
+            add_contour(contours, Vec::from([end.add(offset), extension.add(offset),
+                                             extension.subtract(offset), end.subtract(offset)]));
+        }
+    };
+}
+
+// Fills the gap on the outer side of the corner between the segments previous-current and
+// current-next, the inner side is already covered by the overlapping segments
+fn add_join(contours: &mut Vec<Vec<FloatPoint>>, previous: FloatPoint, current: FloatPoint, next: FloatPoint,
+            half_width: f64, stroke: &Stroke, tolerance: f64) {
+    let incoming = current.subtract(previous);
+    let outgoing = next.subtract(current);
+    let cross_product = incoming.x * outgoing.y - incoming.y * outgoing.x;
+    let dot_product = incoming.x * outgoing.x + incoming.y * outgoing.y;
+
+    if cross_product == 0.0 && dot_product > 0.0 {
+        return;
+    }
+
+    let outer_side = if cross_product > 0.0 { -1.0 } else { 1.0 };
+    let incoming_normal = incoming.normal().unwrap().scale(outer_side);
+    let outgoing_normal = outgoing.normal().unwrap().scale(outer_side);
+    let incoming_corner = current.add(incoming_normal.scale(half_width));
+    let outgoing_corner = current.add(outgoing_normal.scale(half_width));
+
+    if stroke.get_join() == LineJoin::Round {
+        add_contour(contours, get_circle_points(current, half_width, tolerance));
+        return;
+    }
+
+    // The ratio between the miter length and the stroke width is 1 / sin(angle / 2), where angle is
+    // the angle between the segments, which is also 2 / |incoming_normal + outgoing_normal|
+    let normals_sum = incoming_normal.add(outgoing_normal);
+    let is_miter = stroke.get_join() == LineJoin::Miter && normals_sum.length() > 0.0
+                   && 2.0 / normals_sum.length() <= stroke.get_miter_limit();
+
+    if is_miter {
+        let tip = current.add(normals_sum.scale(2.0 * half_width / normals_sum.length().powi(2)));
+        add_contour(contours, Vec::from([current, incoming_corner, tip, outgoing_corner]));
+    } else {
+        add_contour(contours, Vec::from([current, incoming_corner, outgoing_corner]));
+    }
+}
+
+// Keeps all contours oriented the same way as the circles from get_circle_points
+fn add_contour(contours: &mut Vec<Vec<FloatPoint>>, mut contour: Vec<FloatPoint>) {
+    if get_signed_area(&contour) > 0.0 {
+        contour.reverse();
+    }
+    contours.push(contour);
+}
+
+// Shoelace formula
+// https://en.wikipedia.org/wiki/Shoelace_formula
+fn get_signed_area(points: &[FloatPoint]) -> f64 {
+    let mut area = 0.0;
+    for point_index in 0..points.len() {
+        let p0 = points[point_index];
+        let p1 = points[(point_index + 1) % points.len()];
+        area += p0.x * p1.y - p1.x * p0.y;
+    }
+    return area / 2.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fill_rule::FillRule;
+    use crate::rasterizer::scanline::get_polygon_spans;
+
+    const TOLERANCE: f64 = 0.1;
+
+    fn get_stroke(width: u32, cap: LineCap, join: LineJoin, miter_limit: f64) -> Stroke {
+        return Stroke::new(width, cap, join, miter_limit, Vec::new(), 0.0);
+    }
+
+    fn is_pixel_stroked(points: &[FloatPoint], is_closed: bool, stroke: &Stroke, x: i32, y: i32) -> bool {
+        let contours = get_polyline_stroke_contours(points, is_closed, stroke, TOLERANCE);
+        let spans = get_polygon_spans(&contours, FillRule::NonZero, 50, 50);
+        return spans.iter().any(|span| span.y == y && span.x_start <= x && x <= span.x_end);
+    }
+
+    // Horizontal segment from (10, 10) to (30, 10)
+    fn get_segment() -> Vec<FloatPoint> {
+        return Vec::from([FloatPoint::new(10.0, 10.0), FloatPoint::new(30.0, 10.0)]);
+    }
+
+    // Right angle turning down at (30, 10), the outer side of the corner is at the top right
+    fn get_corner() -> Vec<FloatPoint> {
+        return Vec::from([FloatPoint::new(10.0, 10.0), FloatPoint::new(30.0, 10.0), FloatPoint::new(30.0, 30.0)]);
+    }
+
+    #[test]
+    fn segments_are_as_wide_as_the_stroke() {
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Miter, 4.0);
+        assert!(is_pixel_stroked(&get_segment(), false, &stroke, 20, 7));
+        assert!(is_pixel_stroked(&get_segment(), false, &stroke, 20, 12));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 20, 6));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 20, 13));
+    }
+
+    #[test]
+    fn butt_caps_end_at_the_end_points() {
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Miter, 4.0);
+        assert!(is_pixel_stroked(&get_segment(), false, &stroke, 10, 10));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 9, 10));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 30, 10));
+    }
+
+    #[test]
+    fn square_caps_extend_by_half_the_width() {
+        let stroke = get_stroke(6, LineCap::Square, LineJoin::Miter, 4.0);
+        assert!(is_pixel_stroked(&get_segment(), false, &stroke, 7, 7));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 6, 10));
+        assert!(is_pixel_stroked(&get_segment(), false, &stroke, 32, 12));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 33, 10));
+    }
+
+    #[test]
+    fn round_caps_are_half_circles() {
+        let stroke = get_stroke(6, LineCap::Round, LineJoin::Miter, 4.0);
+        assert!(is_pixel_stroked(&get_segment(), false, &stroke, 8, 10));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 7, 7));
+        assert!(is_pixel_stroked(&get_segment(), false, &stroke, 32, 12));
+        assert!(!is_pixel_stroked(&get_segment(), false, &stroke, 33, 12));
+    }
+
+    #[test]
+    fn caps_are_not_added_to_closed_outlines() {
+        let square = Vec::from([FloatPoint::new(10.0, 10.0), FloatPoint::new(30.0, 10.0),
+                                FloatPoint::new(30.0, 30.0), FloatPoint::new(10.0, 30.0)]);
+        let stroke = get_stroke(6, LineCap::Square, LineJoin::Bevel, 4.0);
+        assert!(is_pixel_stroked(&square, false, &stroke, 8, 8));
+        assert!(!is_pixel_stroked(&square, true, &stroke, 8, 8));
+    }
+
+    #[test]
+    fn miter_joins_fill_the_outer_corner() {
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Miter, 4.0);
+        assert!(is_pixel_stroked(&get_corner(), false, &stroke, 32, 8));
+        assert!(is_pixel_stroked(&get_corner(), false, &stroke, 31, 9));
+    }
+
+    #[test]
+    fn bevel_joins_cut_the_outer_corner() {
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Bevel, 4.0);
+        assert!(!is_pixel_stroked(&get_corner(), false, &stroke, 32, 8));
+        assert!(is_pixel_stroked(&get_corner(), false, &stroke, 31, 9));
+    }
+
+    #[test]
+    fn round_joins_round_the_outer_corner() {
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Round, 4.0);
+        assert!(is_pixel_stroked(&get_corner(), false, &stroke, 32, 8));
+        assert!(!is_pixel_stroked(&get_corner(), false, &stroke, 32, 7));
+        assert!(is_pixel_stroked(&get_corner(), false, &stroke, 31, 9));
+    }
+
+    #[test]
+    fn miter_joins_longer_than_the_limit_become_bevel_joins() {
+        // A right angle miter is sqrt(2) times as long as the stroke is wide
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Miter, 1.4);
+        assert!(!is_pixel_stroked(&get_corner(), false, &stroke, 32, 8));
+        assert!(is_pixel_stroked(&get_corner(), false, &stroke, 31, 9));
+
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Miter, 1.5);
+        assert!(is_pixel_stroked(&get_corner(), false, &stroke, 32, 8));
+    }
+
+    #[test]
+    fn empty_outlines_have_no_contours() {
+        let stroke = get_stroke(6, LineCap::Round, LineJoin::Round, 4.0);
+        assert!(get_polyline_stroke_contours(&[], false, &stroke, TOLERANCE).is_empty());
+    }
+
+    #[test]
+    fn single_points_only_have_caps() {
+        let point = [FloatPoint::new(10.0, 10.0), FloatPoint::new(10.0, 10.0)];
+        let stroke = get_stroke(6, LineCap::Butt, LineJoin::Miter, 4.0);
+        assert!(get_polyline_stroke_contours(&point, false, &stroke, TOLERANCE).is_empty());
+
+        let stroke = get_stroke(6, LineCap::Square, LineJoin::Miter, 4.0);
+        assert!(is_pixel_stroked(&point, false, &stroke, 8, 8));
+        assert!(!is_pixel_stroked(&point, false, &stroke, 13, 10));
+    }
+}
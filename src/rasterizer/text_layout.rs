@@ -0,0 +1,69 @@
+
+use crate::utils::{ float_point::FloatPoint, text_alignment::{ TextAlignment, VerticalAlignment } };
+
+use std::vec::Vec;
+
+// Vertical measures of a font at a given size, in pixels
+pub struct FontMetrics {
+    // Height above the baseline
+    pub ascent: f64,
+    // Depth below the baseline
+    pub descent: f64,
+    // Distance between the baselines of consecutive lines
+    pub line_advance: f64
+}
+
+// Size of the box around a laid out text, in pixels, the widest line gives its width
+pub struct TextExtents {
+    pub height: f64,
+    // Distance from the top of the box to the baseline of the first line
+    pub baseline: f64,
+    pub line_widths: Vec<f64>
+}
+
+// Source of glyph outlines, the contours are filled with the non-zero rule
+pub trait Font {
+    fn get_metrics(&self, size: f64) -> FontMetrics;
+    fn get_line_width(&self, line: &str, size: f64) -> f64;
+    // Contours of the glyphs of a line starting from the origin, which is on the baseline. Curved glyphs are
+    // flattened with the given tolerance.
+    fn add_line_contours(&self, line: &str, origin: FloatPoint, size: f64, tolerance: f64,
+                         contours: &mut Vec<Vec<FloatPoint>>);
+}
+
+// Lines are separated by newlines
+pub fn get_text_extents(font: &dyn Font, text: &str, size: f64) -> TextExtents {
+    let metrics = font.get_metrics(size);
+    let line_widths: Vec<f64> = text.split('\n').map(|line| font.get_line_width(line, size)).collect();
+    let height = metrics.ascent + metrics.descent + ((line_widths.len() - 1) as f64) * metrics.line_advance;
+
+    return TextExtents{height: height, baseline: metrics.ascent, line_widths: line_widths};
+}
+
+// Contours of every glyph of the text, aligned with the position on both axes
+pub fn get_text_contours(font: &dyn Font, text: &str, position: FloatPoint, size: f64, alignment: TextAlignment,
+                         vertical_alignment: VerticalAlignment, tolerance: f64) -> Vec<Vec<FloatPoint>> {
+    let metrics = font.get_metrics(size);
+    let extents = get_text_extents(font, text, size);
+    let top = match vertical_alignment {
+        VerticalAlignment::Top => position.y,
+        VerticalAlignment::Middle => position.y - extents.height / 2.0,
+        VerticalAlignment::Baseline => position.y - extents.baseline,
+        VerticalAlignment::Bottom => position.y - extents.height
+    };
+
+    let mut contours: Vec<Vec<FloatPoint>> = Vec::new();
+    for (line_index, (line, line_width)) in text.split('\n').zip(extents.line_widths.iter()).enumerate() {
+        let left = match alignment {
+            TextAlignment::Left => position.x,
+            TextAlignment::Center => position.x - line_width / 2.0,
+            TextAlignment::Right => position.x - line_width
+        };
+        let baseline = top + extents.baseline + (line_index as f64) * metrics.line_advance;
+
+        // Pixel centers are on integer coordinates, so the top left pixel of the text is the one at the
+        // position
+        font.add_line_contours(line, FloatPoint::new(left - 0.5, baseline - 0.5), size, tolerance, &mut contours);
+    }
+    return contours;
+}
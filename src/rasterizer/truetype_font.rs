@@ -0,0 +1,160 @@
+
+use crate::rasterizer::{ text_layout::{ Font, FontMetrics }, flattening::get_path_polylines };
+use crate::utils::{ float_point::FloatPoint, path_command::PathCommand };
+
+use std::vec::Vec;
+
+extern crate ttf_parser;
+use ttf_parser::{ Face, GlyphId, OutlineBuilder, Tag, gpos::{ PairAdjustment, PositioningSubtable } };
+
+// TrueType or OpenType font, the size is the height of its em square
+pub struct TrueTypeFont<'a> {
+    face: Face<'a>
+}
+
+impl<'a> TrueTypeFont<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        return Face::parse(data, 0).ok().map(|face| TrueTypeFont{face: face});
+    }
+
+    fn get_scale(&self, size: f64) -> f64 {
+        return size / (self.face.units_per_em() as f64);
+    }
+
+    // Characters missing from the font use its first glyph, which is meant for them
+    fn get_glyph(&self, character: char) -> GlyphId {
+        return self.face.glyph_index(character).unwrap_or(GlyphId(0));
+    }
+
+    // Pen positions of the glyphs of a line in font units, including kerning, followed by the line width
+    fn get_glyph_positions(&self, line: &str) -> (Vec<(GlyphId, f64)>, f64) {
+        let mut positions: Vec<(GlyphId, f64)> = Vec::new();
+        let mut pen = 0.0;
+        let mut previous: Option<GlyphId> = None;
+
+        for character in line.chars() {
+            let glyph = self.get_glyph(character);
+            if let Some(previous) = previous {
+                pen += self.get_kerning(previous, glyph) as f64;
+            }
+            positions.push((glyph, pen));
+            pen += self.face.glyph_hor_advance(glyph).unwrap_or(0) as f64;
+            previous = Some(glyph);
+        }
+        return (positions, pen);
+    }
+
+    // Adjustment between two glyphs from the legacy kern table, or else from the kern feature of the GPOS table
+    fn get_kerning(&self, left: GlyphId, right: GlyphId) -> i16 {
+        if let Some(kern) = self.face.tables().kern {
+            let kerning = kern.subtables.into_iter()
+                              .filter(|subtable| subtable.horizontal && !subtable.variable)
+                              .find_map(|subtable| subtable.glyphs_kerning(left, right));
+            if let Some(kerning) = kerning {
+                return kerning;
+            }
+        }
+
+        let gpos = match self.face.tables().gpos {
+            None => return 0,
+            Some(gpos) => gpos
+        };
+        for feature in gpos.features {
+            if feature.tag != Tag::from_bytes(b"kern") {
+                continue;
+            }
+
+            for lookup_index in feature.lookup_indices {
+                let lookup = match gpos.lookups.get(lookup_index) {
+                    None => continue,
+                    Some(lookup) => lookup
+                };
+
+                for subtable in lookup.subtables.into_iter::<PositioningSubtable>() {
+                    let pair_adjustment = match subtable {
+                        PositioningSubtable::Pair(pair_adjustment) => pair_adjustment,
+                        _ => continue
+                    };
+                    let coverage_index = match pair_adjustment.coverage().get(left) {
+                        None => continue,
+                        Some(coverage_index) => coverage_index
+                    };
+
+                    let records = match pair_adjustment {
+                        PairAdjustment::Format1 { sets, .. } => sets.get(coverage_index).and_then(|set| set.get(right)),
+                        PairAdjustment::Format2 { classes, matrix, .. } => {
+                            matrix.get((classes.0.get(left), classes.1.get(right)))
+                        }
+                    };
+                    if let Some((left_record, _)) = records {
+                        return left_record.x_advance;
+                    }
+                }
+            }
+        }
+        return 0;
+    }
+}
+
+impl Font for TrueTypeFont<'_> {
+    fn get_metrics(&self, size: f64) -> FontMetrics {
+        let scale = self.get_scale(size);
+        let ascent = (self.face.ascender() as f64) * scale;
+        let descent = -(self.face.descender() as f64) * scale;
+        return FontMetrics{ascent: ascent, descent: descent,
+                           line_advance: ascent + descent + (self.face.line_gap() as f64) * scale};
+    }
+
+    fn get_line_width(&self, line: &str, size: f64) -> f64 {
+        let (_, width) = self.get_glyph_positions(line);
+        return width * self.get_scale(size);
+    }
+
+    fn add_line_contours(&self, line: &str, origin: FloatPoint, size: f64, tolerance: f64,
+                         contours: &mut Vec<Vec<FloatPoint>>) {
+        let scale = self.get_scale(size);
+        let (positions, _) = self.get_glyph_positions(line);
+
+        for (glyph, pen) in positions {
+            let glyph_origin = FloatPoint::new(origin.x + pen * scale, origin.y);
+            let mut outline = GlyphOutline{commands: Vec::new(), origin: glyph_origin, scale: scale};
+            self.face.outline_glyph(glyph, &mut outline);
+            contours.extend(get_path_polylines(&outline.commands, tolerance).into_iter().map(|(points, _)| points));
+        }
+    }
+}
+
+// Glyph outline converted to path commands in pixels, font units have the y axis pointing up
+struct GlyphOutline {
+    commands: Vec<PathCommand>,
+    origin: FloatPoint,
+    scale: f64
+}
+
+impl GlyphOutline {
+    fn to_canvas(&self, x: f32, y: f32) -> FloatPoint {
+        return FloatPoint::new(self.origin.x + (x as f64) * self.scale, self.origin.y - (y as f64) * self.scale);
+    }
+}
+
+impl OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.commands.push(PathCommand::MoveTo(self.to_canvas(x, y)));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.commands.push(PathCommand::LineTo(self.to_canvas(x, y)));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.commands.push(PathCommand::QuadraticTo(self.to_canvas(x1, y1), self.to_canvas(x, y)));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.commands.push(PathCommand::CubicTo(self.to_canvas(x1, y1), self.to_canvas(x2, y2), self.to_canvas(x, y)));
+    }
+
+    fn close(&mut self) {
+        self.commands.push(PathCommand::Close);
+    }
+}
@@ -7,19 +7,74 @@ use crate::shapes::{ shape::Shape,
                      polygon::Polygon,
                      rectangle::Rectangle,
                      square::Square,
-                     triangle::Triangle };
+                     triangle::Triangle,
+                     ellipse::Ellipse,
+                     arc::Arc,
+                     pie::Pie,
+                     path::Path,
+                     regular_polygon::RegularPolygon,
+                     star::Star,
+                     polyline::Polyline,
+                     image::{ Image, MAX_IMAGE_SIZE },
+                     text::Text,
+                     transformed_shape::TransformedShape,
+                     group::Group,
+                     clip::{ Clip, ClipRegion },
+                     soft_mask::{ SoftMask, MaskMode },
+                     blended_shape::BlendedShape,
+                     filtered_shape::FilteredShape };
 
 use crate::utils::{ generic_error::GenericError,
                     color::Color,
-                    point::Point };
+                    point::Point,
+                    fill_rule::FillRule,
+                    float_point::FloatPoint,
+                    corner_radii::CornerRadii,
+                    path_command::PathCommand,
+                    paint::{ Paint, ColorStop, Pattern },
+                    text_alignment::{ TextAlignment, VerticalAlignment },
+                    transform::Transform,
+                    blend_mode::BlendMode,
+                    effect::{ Effect, MAX_BLUR_RADIUS },
+                    layer::Layer,
+                    stroke::{ Stroke, LineCap, LineJoin } };
+use crate::rasterizer::dash::MIN_DASH_PATTERN_LENGTH;
 use std::string::String;
 use std::str::SplitWhitespace;
 use std::error::Error;
 
+// More sides or points than this would not be told apart from a circle and only cost memory
+const MAX_INSCRIBED_VERTICES: u32 = 10000;
+
 fn create_parse_error(name: String) -> Box<dyn Error> {
     return Box::new(GenericError::new(name));
 }
 
+// Attributes supported by every shape except the canvas, applied by wrapping the parsed shape
+#[derive(Default)]
+struct CommonAttributes {
+    transform: Transform,
+    blend_mode: BlendMode,
+    effects: Vec<Effect>
+}
+
+impl CommonAttributes {
+    fn wrap_shape(self, shape: Box<dyn Shape>) -> Box<dyn Shape> {
+        let mut shape = shape;
+        if !self.transform.is_identity() {
+            shape = Box::new(TransformedShape::new(shape, self.transform));
+        }
+        // Effects filter the transformed shape and are blended with it
+        if !self.effects.is_empty() {
+            shape = Box::new(FilteredShape::new(shape, self.effects));
+        }
+        if self.blend_mode != BlendMode::Normal {
+            shape = Box::new(BlendedShape::new(shape, self.blend_mode));
+        }
+        return shape;
+    }
+}
+
 pub struct ShapeFactory {}
 
 impl ShapeFactory {
@@ -34,26 +89,111 @@ impl ShapeFactory {
         
         if first_element == "CANVAS" {
             return parse_canvas(&mut line_split_iterator);
-        } else if first_element == "LINE" {
-            return parse_line(&mut line_split_iterator);
+        }
+
+        let mut common_attributes = CommonAttributes::default();
+        let shape = if first_element == "LINE" {
+            parse_line(&mut line_split_iterator, &mut common_attributes)?
         } else if first_element == "SQUARE" {
-            return parse_square(&mut line_split_iterator);
+            parse_square(&mut line_split_iterator, &mut common_attributes)?
         } else if first_element == "RECTANGLE" {
-            return parse_rectangle(&mut line_split_iterator);
+            parse_rectangle(&mut line_split_iterator, &mut common_attributes)?
         } else if first_element == "CIRCLE" {
-            return parse_circle(&mut line_split_iterator);
+            parse_circle(&mut line_split_iterator, &mut common_attributes)?
         } else if first_element == "TRIANGLE" {
-            return parse_triangle(&mut line_split_iterator);
+            parse_triangle(&mut line_split_iterator, &mut common_attributes)?
         } else if first_element == "DIAMOND" {
-            return parse_diamond(&mut line_split_iterator);
+            parse_diamond(&mut line_split_iterator, &mut common_attributes)?
         } else if first_element == "POLYGON" {
-            return parse_polygon(&mut line_split_iterator);
+            parse_polygon(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "ELLIPSE" {
+            parse_ellipse(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "ARC" {
+            parse_arc(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "PIE" {
+            parse_pie(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "PATH" {
+            parse_path(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "REGULAR_POLYGON" {
+            parse_regular_polygon(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "STAR" {
+            parse_star(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "POLYLINE" {
+            parse_polyline(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "IMAGE" {
+            parse_image(&mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "TEXT" {
+            parse_text(line, &mut line_split_iterator, &mut common_attributes)?
+        } else if first_element == "CLIP" {
+            parse_clip(&mut line_split_iterator, &mut common_attributes)?
+        } else {
+            let error_string = format!("Invalid line format: {}", line);
+            return Err(Box::new(GenericError::new(error_string)));
+        };
+        return Ok(common_attributes.wrap_shape(shape));
+    }
+
+    // Groups and soft masks span several lines, from a GROUP or MASK line with their attributes to the matching
+    // END line
+    pub fn starts_block(line: &str) -> bool {
+        let first_element = line.split_whitespace().next();
+        return first_element == Some("GROUP") || first_element == Some("MASK");
+    }
+
+    pub fn ends_block(line: &str) -> bool {
+        return line.split_whitespace().next() == Some("END");
+    }
+
+    pub fn is_canvas(line: &str) -> bool {
+        return line.split_whitespace().next() == Some("CANVAS");
+    }
+
+    // Layer started by a LAYER line with the flags of that line, none for the other lines
+    pub fn parse_layer(line: &str) -> Result<Option<Layer>, Box<dyn Error>> {
+        let mut line_split_iterator = line.split_whitespace();
+        if line_split_iterator.next() != Some("LAYER") {
+            return Ok(None);
         }
 
-        let error_string = format!("Invalid line format: {}", line);
-        let error = GenericError::new(error_string);
-        return Err(Box::new(error));
-    } 
+        let name = match line_split_iterator.next() {
+            None => return Err(create_parse_error(String::from("Layer does not seem to have a name"))),
+            Some(name) => String::from(name)
+        };
+        let (mut is_hidden, mut is_locked) = (false, false);
+        for keyword in line_split_iterator {
+            match keyword {
+                "HIDDEN" => is_hidden = true,
+                "LOCKED" => is_locked = true,
+                other => {
+                    return Err(create_parse_error(format!("Layer {} does not support attribute {}", name, other)));
+                }
+            };
+        }
+        return Ok(Some(Layer::new(name, is_hidden, is_locked)));
+    }
+
+    // The canvas sets the size of the image, so it cannot be part of a block
+    pub fn parse_block_child(line: &String) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+        if ShapeFactory::is_canvas(line) {
+            return Err(create_parse_error(format!("Canvas cannot be part of a group or mask: {}", line)));
+        }
+        return ShapeFactory::parse_shape(line);
+    }
+
+    pub fn parse_block(line: &String, shapes: Vec<Box<dyn Shape>>) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+        let mut line_split_iterator = line.split_whitespace();
+        let first_element = line_split_iterator.next();
+
+        let mut common_attributes = CommonAttributes::default();
+        let block = if first_element == Some("GROUP") {
+            parse_group(&mut line_split_iterator, shapes, &mut common_attributes)?
+        } else if first_element == Some("MASK") {
+            parse_soft_mask(&mut line_split_iterator, shapes, &mut common_attributes)?
+        } else {
+            return Err(create_parse_error(format!("Invalid group or mask format: {}", line)));
+        };
+        return Ok(common_attributes.wrap_shape(block));
+    }
 }
 
 fn parse_color_hex(line_split: &mut SplitWhitespace, shape: &String) -> Result<[u8; 3], Box<dyn Error>> {
@@ -85,7 +225,7 @@ fn parse_u8(line_split: &mut SplitWhitespace, name: &String, attribute: &String)
         None => {
             return Err(create_parse_error(format!("{} does not seem to have a {}", name, attribute)));
         },
-        Some(height_str) => match u8::from_str_radix(height_str, 10) {
+        Some(height_str) => match height_str.parse::<u8>() {
             Ok(num) => return Ok(num),
             Err(err) => {
                 return Err(create_parse_error(format!("Could not convert {}'s {} to u8: {}", name, attribute, err)));
@@ -99,7 +239,7 @@ fn parse_i32(line_split: &mut SplitWhitespace, name: &String, attribute: &String
         None => {
             return Err(create_parse_error(format!("{} does not seem to have a {}", name, attribute)));
         },
-        Some(height_str) => match i32::from_str_radix(height_str, 10) {
+        Some(height_str) => match height_str.parse::<i32>() {
             Ok(num) => return Ok(num),
             Err(err) => {
                 return Err(create_parse_error(format!("Could not convert {}'s {} to i32: {}", name, attribute, err)));
@@ -113,7 +253,7 @@ fn parse_u32(line_split: &mut SplitWhitespace, name: &String, attribute: &String
         None => {
             return Err(create_parse_error(format!("{} does not seem to have a {}", name, attribute)));
         },
-        Some(height_str) => match u32::from_str_radix(height_str, 10) {
+        Some(height_str) => match height_str.parse::<u32>() {
             Ok(num) => return Ok(num),
             Err(err) => {
                 return Err(create_parse_error(format!("Could not convert {}'s {} to u32: {}", name, attribute, err)));
@@ -122,12 +262,116 @@ fn parse_u32(line_split: &mut SplitWhitespace, name: &String, attribute: &String
     }
 }
 
+fn parse_f64(line_split: &mut SplitWhitespace, name: &String, attribute: &String) -> Result<f64, Box<dyn Error>> {
+    match line_split.next() {
+        None => {
+            return Err(create_parse_error(format!("{} does not seem to have a {}", name, attribute)));
+        },
+        Some(number_str) => match number_str.parse::<f64>() {
+            Ok(num) if num.is_finite() => return Ok(num),
+            Ok(num) => {
+                return Err(create_parse_error(format!("{}'s {} is not a finite number: {}", name, attribute, num)));
+            },
+            Err(err) => {
+                return Err(create_parse_error(format!("Could not convert {}'s {} to f64: {}", name, attribute, err)));
+            }
+        }
+    }
+}
+
 fn parse_color(line_split: &mut SplitWhitespace, shape: &String, attribute: &String) -> Result<Color, Box<dyn Error>>{
     let [r, g, b] = parse_color_hex(line_split, shape)?;
     let a = parse_u8(line_split, &format!("{}'s {}", shape, attribute), &String::from("alpha"))?;
     return Ok(Color::new(r, g, b, a));
 }
 
+// A color, a gradient introduced by the LINEAR or RADIAL keyword, a pattern introduced by the HATCH,
+// CROSSHATCH or DOTS keyword or an image introduced by the TEXTURE keyword
+fn parse_paint(line_split: &mut SplitWhitespace, shape: &String, attribute: &String) -> Result<Paint, Box<dyn Error>> {
+    let paint_name = format!("{}'s {}", shape, attribute);
+    match line_split.clone().next() {
+        Some("LINEAR") => {
+            line_split.next();
+            let start = parse_float_point(line_split, &paint_name, &String::from("start"))?;
+            let end = parse_float_point(line_split, &paint_name, &String::from("end"))?;
+            let stops = parse_color_stops(line_split, &paint_name)?;
+            return Ok(Paint::LinearGradient { start: start, end: end, stops: stops });
+        },
+        Some("RADIAL") => {
+            line_split.next();
+            let center = parse_float_point(line_split, &paint_name, &String::from("center"))?;
+            let radius = parse_f64(line_split, &paint_name, &String::from("radius"))?;
+            if radius < 0.0 {
+                return Err(create_parse_error(format!("{}'s radius must not be negative, found {}",
+                                                      paint_name, radius)));
+            }
+            let stops = parse_color_stops(line_split, &paint_name)?;
+            return Ok(Paint::RadialGradient { center: center, radius: radius, stops: stops });
+        },
+        Some("HATCH") => {
+            line_split.next();
+            return parse_pattern(line_split, &paint_name, Pattern::Hatch);
+        },
+        Some("CROSSHATCH") => {
+            line_split.next();
+            return parse_pattern(line_split, &paint_name, Pattern::CrossHatch);
+        },
+        Some("DOTS") => {
+            line_split.next();
+            return parse_pattern(line_split, &paint_name, Pattern::Dots);
+        },
+        Some("TEXTURE") => {
+            line_split.next();
+            let path = parse_path_string(line_split, &paint_name)?;
+            let origin = parse_float_point(line_split, &paint_name, &String::from("origin"))?;
+            return Ok(Paint::Texture { path: path, origin: origin });
+        },
+        _ => return Ok(Paint::Solid(parse_color(line_split, shape, attribute)?))
+    }
+}
+
+fn parse_pattern(line_split: &mut SplitWhitespace, paint_name: &String, pattern: Pattern)
+-> Result<Paint, Box<dyn Error>> {
+    let angle = parse_f64(line_split, paint_name, &String::from("angle"))?;
+    let spacing = parse_f64(line_split, paint_name, &String::from("spacing"))?;
+    if spacing <= 0.0 {
+        return Err(create_parse_error(format!("{}'s spacing must be positive, found {}", paint_name, spacing)));
+    }
+    let line_width = parse_f64(line_split, paint_name, &String::from("line width"))?;
+    if line_width <= 0.0 {
+        return Err(create_parse_error(format!("{}'s line width must be positive, found {}",
+                                              paint_name, line_width)));
+    }
+    let foreground = parse_color(line_split, paint_name, &String::from("foreground color"))?;
+    let background = parse_color(line_split, paint_name, &String::from("background color"))?;
+    return Ok(Paint::Pattern { pattern: pattern, angle: angle, spacing: spacing, line_width: line_width,
+                               foreground: foreground, background: background });
+}
+
+// Number of stops followed by an offset between 0 and 100 and a color for every stop. Like in SVG, an
+// offset smaller than the previous one is raised to it.
+fn parse_color_stops(line_split: &mut SplitWhitespace, paint_name: &String) -> Result<Vec<ColorStop>, Box<dyn Error>> {
+    let stops_number = parse_u32(line_split, paint_name, &String::from("number of stops"))?;
+    if stops_number == 0 {
+        return Err(create_parse_error(format!("{} must have at least one color stop", paint_name)));
+    }
+
+    let mut stops: Vec<ColorStop> = Vec::new();
+    for stop_index in 0..stops_number {
+        let stop_name = format!("stop {}", stop_index);
+        let offset = parse_f64(line_split, paint_name, &format!("{} offset", stop_name))?;
+        if !(0.0..=100.0).contains(&offset) {
+            return Err(create_parse_error(format!("{}'s {} offset must be between 0 and 100, found {}",
+                                                  paint_name, stop_name, offset)));
+        }
+        let color = parse_color(line_split, paint_name, &format!("{} color", stop_name))?;
+
+        let offset = stops.last().map_or(offset / 100.0, |last| f64::max(last.offset, offset / 100.0));
+        stops.push(ColorStop::new(offset, color));
+    }
+    return Ok(stops);
+}
+
 fn parse_point(line_split: &mut SplitWhitespace, shape: &String, attribute: &String) -> Result<Point, Box<dyn Error>> {
     let point_name = format!("{}.{}", shape, attribute);
     let x = parse_i32(line_split, &point_name, &String::from("x"))?;
@@ -135,6 +379,362 @@ fn parse_point(line_split: &mut SplitWhitespace, shape: &String, attribute: &Str
     return Ok(Point::new(x, y));
 }
 
+fn parse_path_string(line_split: &mut SplitWhitespace, name: &String) -> Result<String, Box<dyn Error>> {
+    return match line_split.next() {
+        None => Err(create_parse_error(format!("{} does not seem to have a file path", name))),
+        Some(path) => Ok(String::from(path))
+    };
+}
+
+// String between double quotes which may contain whitespaces, \" for a quote, \\ for a backslash and \n
+// for a newline. The iterator continues after the closing quote.
+fn parse_quoted_string<'a>(line: &'a str, line_split: &mut SplitWhitespace<'a>, shape: &String, attribute: &String)
+-> Result<String, Box<dyn Error>> {
+    let first_token = match line_split.next() {
+        Some(token) if token.starts_with('"') => token,
+        _ => return Err(create_parse_error(format!("{} does not seem to have a quoted {}", shape, attribute)))
+    };
+
+    // Every token is a slice of the line, so its offset gives where the string starts
+    let start = (first_token.as_ptr() as usize) - (line.as_ptr() as usize) + 1;
+    let mut content = String::new();
+    let mut characters = line[start..].char_indices();
+
+    while let Some((index, character)) = characters.next() {
+        match character {
+            '"' => {
+                *line_split = line[start + index + 1..].split_whitespace();
+                return Ok(content);
+            },
+            '\\' => match characters.next() {
+                Some((_, 'n')) => content.push('\n'),
+                Some((_, escaped)) => content.push(escaped),
+                None => content.push('\\')
+            },
+            _ => content.push(character)
+        }
+    }
+    return Err(create_parse_error(format!("{}'s {} does not have a closing quote", shape, attribute)));
+}
+
+fn parse_float_point(line_split: &mut SplitWhitespace, shape: &String, attribute: &String)
+-> Result<FloatPoint, Box<dyn Error>> {
+    let point_name = format!("{}.{}", shape, attribute);
+    let x = parse_f64(line_split, &point_name, &String::from("x"))?;
+    let y = parse_f64(line_split, &point_name, &String::from("y"))?;
+    return Ok(FloatPoint::new(x, y));
+}
+
+// Optional attributes follow the mandatory fields of a shape, each one is a keyword that may be
+// followed by values. The attributes of the shape and the ones every shape supports come in any order,
+// parse_attribute returns false if the shape does not support the keyword.
+fn parse_attributes<F>(line_split: &mut SplitWhitespace, shape: &String, common_attributes: &mut CommonAttributes,
+                       mut parse_attribute: F) -> Result<(), Box<dyn Error>>
+where F: FnMut(&str, &mut SplitWhitespace) -> Result<bool, Box<dyn Error>> {
+    while let Some(keyword) = line_split.next() {
+        if !parse_attribute(keyword, line_split)?
+           && !parse_common_attribute(keyword, line_split, shape, common_attributes)? {
+            return Err(create_parse_error(format!("{} does not support attribute {}", shape, keyword)));
+        }
+    }
+    return Ok(());
+}
+
+fn parse_common_attribute(keyword: &str, line_split: &mut SplitWhitespace, shape: &String,
+                          common_attributes: &mut CommonAttributes) -> Result<bool, Box<dyn Error>> {
+    return Ok(parse_transform_attribute(keyword, line_split, shape, &mut common_attributes.transform)?
+              || parse_blend_mode_attribute(keyword, line_split, shape, &mut common_attributes.blend_mode)?
+              || parse_effect_attribute(keyword, line_split, shape, &mut common_attributes.effects)?);
+}
+
+// Offsets and blur radii are in pixels of the image
+fn parse_effect_attribute(keyword: &str, line_split: &mut SplitWhitespace, shape: &String,
+                          effects: &mut Vec<Effect>) -> Result<bool, Box<dyn Error>> {
+    let parse_radius = |line_split: &mut SplitWhitespace, attribute: &str| -> Result<u32, Box<dyn Error>> {
+        let radius = parse_u32(line_split, shape, &String::from(attribute))?;
+        if radius > MAX_BLUR_RADIUS {
+            return Err(create_parse_error(format!("{}'s {} must be at most {}, found {}", shape, attribute,
+                                                  MAX_BLUR_RADIUS, radius)));
+        }
+        return Ok(radius);
+    };
+    let effect = match keyword {
+        "SHADOW" => {
+            let offset = parse_point(line_split, shape, &String::from("shadow offset"))?;
+            let blur_radius = parse_radius(line_split, "shadow blur radius")?;
+            let color = parse_color(line_split, shape, &String::from("shadow color"))?;
+            Effect::DropShadow { offset: offset, blur_radius: blur_radius, color: color }
+        },
+        "GLOW" => {
+            let blur_radius = parse_radius(line_split, "glow blur radius")?;
+            let color = parse_color(line_split, shape, &String::from("glow color"))?;
+            Effect::OuterGlow { blur_radius: blur_radius, color: color }
+        },
+        "BLUR" => {
+            let radius = parse_radius(line_split, "blur radius")?;
+            Effect::Blur { radius: radius }
+        },
+        _ => return Ok(false)
+    };
+    effects.push(effect);
+    return Ok(true);
+}
+
+fn parse_blend_mode_attribute(keyword: &str, line_split: &mut SplitWhitespace, shape: &String,
+                              blend_mode: &mut BlendMode) -> Result<bool, Box<dyn Error>> {
+    if keyword != "BLEND" {
+        return Ok(false);
+    }
+
+    *blend_mode = match line_split.next() {
+        Some("NORMAL") => BlendMode::Normal,
+        Some("MULTIPLY") => BlendMode::Multiply,
+        Some("SCREEN") => BlendMode::Screen,
+        Some("OVERLAY") => BlendMode::Overlay,
+        Some("DARKEN") => BlendMode::Darken,
+        Some("LIGHTEN") => BlendMode::Lighten,
+        Some("DIFFERENCE") => BlendMode::Difference,
+        Some("CLEAR") => BlendMode::Clear,
+        Some("COPY") => BlendMode::Copy,
+        Some("DESTINATION_OVER") => BlendMode::DestinationOver,
+        Some("SOURCE_IN") => BlendMode::SourceIn,
+        Some("DESTINATION_IN") => BlendMode::DestinationIn,
+        Some("SOURCE_OUT") => BlendMode::SourceOut,
+        Some("DESTINATION_OUT") => BlendMode::DestinationOut,
+        Some("SOURCE_ATOP") => BlendMode::SourceAtop,
+        Some("DESTINATION_ATOP") => BlendMode::DestinationAtop,
+        Some("XOR") => BlendMode::Xor,
+        other => {
+            return Err(create_parse_error(format!("{} blend mode is not supported: {}", shape,
+                                                  other.unwrap_or("nothing"))));
+        }
+    };
+    return Ok(true);
+}
+
+// Transforms are applied one after the other in the order they are written. Angles are in degrees,
+// rotations and scalings are done around the origin unless a center is given.
+fn parse_transform_attribute(keyword: &str, line_split: &mut SplitWhitespace, shape: &String,
+                             transform: &mut Transform) -> Result<bool, Box<dyn Error>> {
+    let get_next_numbers_count = |line_split: &SplitWhitespace| {
+        return line_split.clone().take_while(|token| token.parse::<f64>().is_ok()).count();
+    };
+
+    let next_transform = match keyword {
+        "TRANSLATE" => {
+            let tx = parse_f64(line_split, shape, &String::from("translation x"))?;
+            let ty = parse_f64(line_split, shape, &String::from("translation y"))?;
+            Transform::translate(tx, ty)
+        },
+        "ROTATE" => {
+            let angle = parse_f64(line_split, shape, &String::from("rotation angle"))?;
+            let center = if get_next_numbers_count(line_split) > 0 {
+                parse_float_point(line_split, shape, &String::from("rotation center"))?
+            } else {
+                FloatPoint::new(0.0, 0.0)
+            };
+            Transform::rotate(angle, center)
+        },
+        // One or two factors, optionally followed by the center: the number of values tells them apart
+        "SCALE" => {
+            let values_number = get_next_numbers_count(line_split);
+            let sx = parse_f64(line_split, shape, &String::from("scale x"))?;
+            let sy = if values_number == 2 || values_number >= 4 {
+                parse_f64(line_split, shape, &String::from("scale y"))?
+            } else {
+                sx
+            };
+            let center = if values_number >= 3 {
+                parse_float_point(line_split, shape, &String::from("scale center"))?
+            } else {
+                FloatPoint::new(0.0, 0.0)
+            };
+            Transform::scale(sx, sy, center)
+        },
+        "SKEW" => {
+            let angle_x = parse_f64(line_split, shape, &String::from("skew x angle"))?;
+            let angle_y = parse_f64(line_split, shape, &String::from("skew y angle"))?;
+            Transform::skew(angle_x, angle_y)
+        },
+        _ => return Ok(false)
+    };
+    *transform = transform.then(&next_transform);
+    if !transform.is_invertible() {
+        return Err(create_parse_error(format!("{}'s transforms scale it to nothing or to infinity", shape)));
+    }
+    return Ok(true);
+}
+
+fn parse_fill_rule_attribute(keyword: &str, fill_rule: &mut FillRule) -> bool {
+    return match keyword {
+        "EVENODD" => {
+            *fill_rule = FillRule::EvenOdd;
+            true
+        },
+        "NONZERO" => {
+            *fill_rule = FillRule::NonZero;
+            true
+        },
+        _ => false
+    };
+}
+
+fn parse_stroke_attribute(keyword: &str, line_split: &mut SplitWhitespace, shape: &String, stroke: &mut Stroke)
+-> Result<bool, Box<dyn Error>> {
+    return match keyword {
+        "STROKE_WIDTH" => {
+            let width = parse_u32(line_split, shape, &String::from("stroke width"))?;
+            if width == 0 {
+                return Err(create_parse_error(format!("{}'s stroke width must be positive", shape)));
+            }
+            stroke.set_width(width);
+            Ok(true)
+        },
+        "CAP" => {
+            let cap = match line_split.next() {
+                Some("BUTT") => LineCap::Butt,
+                Some("ROUND") => LineCap::Round,
+                Some("SQUARE") => LineCap::Square,
+                _ => return Err(create_parse_error(format!("{}'s cap must be BUTT, ROUND or SQUARE", shape)))
+            };
+            stroke.set_cap(cap);
+            Ok(true)
+        },
+        "JOIN" => {
+            let join = match line_split.next() {
+                Some("MITER") => LineJoin::Miter,
+                Some("ROUND") => LineJoin::Round,
+                Some("BEVEL") => LineJoin::Bevel,
+                _ => return Err(create_parse_error(format!("{}'s join must be MITER, ROUND or BEVEL", shape)))
+            };
+            stroke.set_join(join);
+            Ok(true)
+        },
+        "MITER_LIMIT" => {
+            let miter_limit = parse_f64(line_split, shape, &String::from("miter limit"))?;
+            if miter_limit < 1.0 {
+                return Err(create_parse_error(format!("{}'s miter limit must be at least 1", shape)));
+            }
+            stroke.set_miter_limit(miter_limit);
+            Ok(true)
+        },
+        "DASH" => {
+            let lengths_number = parse_u32(line_split, shape, &String::from("number of dash lengths"))?;
+            let mut dash_array: Vec<f64> = Vec::new();
+
+            for length_index in 0..lengths_number {
+                let length = parse_f64(line_split, shape, &format!("dash length {}", length_index))?;
+                if length < 0.0 {
+                    return Err(create_parse_error(format!("{}'s dash lengths can not be negative", shape)));
+                }
+                dash_array.push(length);
+            }
+            if !dash_array.is_empty() && dash_array.iter().sum::<f64>() < MIN_DASH_PATTERN_LENGTH {
+                return Err(create_parse_error(format!("{}'s dash lengths must add up to at least {}", shape,
+                                                      MIN_DASH_PATTERN_LENGTH)));
+            }
+            stroke.set_dash_array(dash_array);
+            Ok(true)
+        },
+        "DASH_OFFSET" => {
+            let dash_offset = parse_f64(line_split, shape, &String::from("dash offset"))?;
+            stroke.set_dash_offset(dash_offset);
+            Ok(true)
+        },
+        _ => Ok(false)
+    };
+}
+
+fn parse_rotation_attribute(keyword: &str, line_split: &mut SplitWhitespace, shape: &String, rotation: &mut f64)
+-> Result<bool, Box<dyn Error>> {
+    if keyword != "ROTATION" {
+        return Ok(false);
+    }
+    *rotation = parse_f64(line_split, shape, &String::from("rotation"))?;
+    return Ok(true);
+}
+
+// Attributes of rotatable shapes that have an outline
+fn parse_rotation_and_stroke_attributes(line_split: &mut SplitWhitespace, shape: &String,
+                                        common_attributes: &mut CommonAttributes)
+-> Result<(f64, Stroke), Box<dyn Error>> {
+    let mut rotation = 0.0;
+    let mut stroke = Stroke::default();
+    parse_attributes(line_split, shape, common_attributes, |keyword, line_split| {
+        return Ok(parse_rotation_attribute(keyword, line_split, shape, &mut rotation)?
+                  || parse_stroke_attribute(keyword, line_split, shape, &mut stroke)?);
+    })?;
+    return Ok((rotation, stroke));
+}
+
+fn parse_path_command(keyword: &str, line_split: &mut SplitWhitespace, shape: &String,
+                      commands: &mut Vec<PathCommand>) -> Result<bool, Box<dyn Error>> {
+    let command_name = format!("command {}", commands.len());
+    let command = match keyword {
+        "M" => PathCommand::MoveTo(parse_float_point(line_split, shape, &command_name)?),
+        "L" => PathCommand::LineTo(parse_float_point(line_split, shape, &command_name)?),
+        "Q" => {
+            let control = parse_float_point(line_split, shape, &format!("{} control point", command_name))?;
+            let end = parse_float_point(line_split, shape, &format!("{} end point", command_name))?;
+            PathCommand::QuadraticTo(control, end)
+        },
+        "C" => {
+            let control0 = parse_float_point(line_split, shape, &format!("{} first control point", command_name))?;
+            let control1 = parse_float_point(line_split, shape, &format!("{} second control point", command_name))?;
+            let end = parse_float_point(line_split, shape, &format!("{} end point", command_name))?;
+            PathCommand::CubicTo(control0, control1, end)
+        },
+        "Z" => PathCommand::Close,
+        _ => return Ok(false)
+    };
+
+    if commands.is_empty() && !matches!(command, PathCommand::MoveTo(_)) {
+        return Err(create_parse_error(format!("{} must start with a move-to command, found {}", shape, keyword)));
+    }
+    commands.push(command);
+    return Ok(true);
+}
+
+// CORNER_RADIUS sets all the corners, CORNER_RADII sets them clockwise starting from the top left one
+fn parse_corner_radii_attribute(keyword: &str, line_split: &mut SplitWhitespace, shape: &String,
+                                corner_radii: &mut CornerRadii) -> Result<bool, Box<dyn Error>> {
+    if keyword == "CORNER_RADIUS" {
+        *corner_radii = CornerRadii::uniform(parse_u32(line_split, shape, &String::from("corner radius"))?);
+    } else if keyword == "CORNER_RADII" {
+        let top_left = parse_u32(line_split, shape, &String::from("top left corner radius"))?;
+        let top_right = parse_u32(line_split, shape, &String::from("top right corner radius"))?;
+        let bottom_right = parse_u32(line_split, shape, &String::from("bottom right corner radius"))?;
+        let bottom_left = parse_u32(line_split, shape, &String::from("bottom left corner radius"))?;
+        *corner_radii = CornerRadii::new(top_left, top_right, bottom_right, bottom_left);
+    } else {
+        return Ok(false);
+    }
+    return Ok(true);
+}
+
+// Attributes of rectangles and squares
+fn parse_corner_radii_and_stroke_attributes(line_split: &mut SplitWhitespace, shape: &String,
+                                            common_attributes: &mut CommonAttributes)
+-> Result<(CornerRadii, Stroke), Box<dyn Error>> {
+    let mut corner_radii = CornerRadii::default();
+    let mut stroke = Stroke::default();
+    parse_attributes(line_split, shape, common_attributes, |keyword, line_split| {
+        return Ok(parse_corner_radii_attribute(keyword, line_split, shape, &mut corner_radii)?
+                  || parse_stroke_attribute(keyword, line_split, shape, &mut stroke)?);
+    })?;
+    return Ok((corner_radii, stroke));
+}
+
+// Attributes shared by every shape that has an outline
+fn parse_stroke_attributes(line_split: &mut SplitWhitespace, shape: &String, common_attributes: &mut CommonAttributes)
+-> Result<Stroke, Box<dyn Error>> {
+    let mut stroke = Stroke::default();
+    parse_attributes(line_split, shape, common_attributes, |keyword, line_split| {
+        return parse_stroke_attribute(keyword, line_split, shape, &mut stroke);
+    })?;
+    return Ok(stroke);
+}
+
 fn parse_canvas(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
     let height = parse_u32(line_split, &String::from("Canvas"), &String::from("height"))?;
     let width = parse_u32(line_split, &String::from("Canvas"), &String::from("width"))?;
@@ -142,67 +742,421 @@ fn parse_canvas(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<
     return Ok(Box::new(Canvas::new(height, width, color)));
 }
 
-fn parse_line(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+fn parse_line(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
     let p0 = parse_point(line_split, &String::from("Line"), &String::from("first point"))?;
     let p1 = parse_point(line_split, &String::from("Line"), &String::from("second point"))?;
     let color = parse_color(line_split, &String::from("Line"), &String::from("color"))?;
-    return Ok(Box::new(Line::new(p0, p1, color)));
+    let stroke = parse_stroke_attributes(line_split, &String::from("Line"), common_attributes)?;
+    return Ok(Box::new(Line::new(p0, p1, color, stroke)));
 }
 
-fn parse_square(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+fn parse_square(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
     let top_left = parse_point(line_split, &String::from("Square"), &String::from("top left"))?;
     let side = parse_u32(line_split, &String::from("Square"), &String::from("side"))?;
     let outline_color = parse_color(line_split, &String::from("Square"), &String::from("outline color"))?;
-    let fill_color = parse_color(line_split, &String::from("Square"), &String::from("fill color"))?;
-    return Ok(Box::new(Square::new(top_left, side, outline_color, fill_color)));
+    let fill_paint = parse_paint(line_split, &String::from("Square"), &String::from("fill paint"))?;
+    let (corner_radii, stroke) = parse_corner_radii_and_stroke_attributes(line_split, &String::from("Square"),
+                                                                          common_attributes)?;
+    return Ok(Box::new(Square::new(top_left, side, outline_color, fill_paint, corner_radii, stroke)));
 }
 
-fn parse_rectangle(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+fn parse_rectangle(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
     let top_left = parse_point(line_split, &String::from("Rectangle"), &String::from("top left"))?;
     let height = parse_u32(line_split, &String::from("Rectangle"), &String::from("height"))?;
     let width = parse_u32(line_split, &String::from("Rectangle"), &String::from("width"))?;
     let outline_color = parse_color(line_split, &String::from("Rectangle"), &String::from("outline color"))?;
-    let fill_color = parse_color(line_split, &String::from("Rectangle"), &String::from("fill color"))?;
-    return Ok(Box::new(Rectangle::new(top_left, height, width, outline_color, fill_color)));
+    let fill_paint = parse_paint(line_split, &String::from("Rectangle"), &String::from("fill paint"))?;
+    let (corner_radii, stroke) = parse_corner_radii_and_stroke_attributes(line_split,
+                                                                          &String::from("Rectangle"),
+                                                                          common_attributes)?;
+    return Ok(Box::new(Rectangle::new(top_left, height, width, outline_color, fill_paint, corner_radii,
+                                      stroke)));
 }
 
-fn parse_circle(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+fn parse_circle(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
     let center = parse_point(line_split, &String::from("Circle"), &String::from("center"))?;
     let radius = parse_u32(line_split, &String::from("Circle"), &String::from("radius"))?;
     let outline_color = parse_color(line_split, &String::from("Circle"), &String::from("outline color"))?;
-    let fill_color = parse_color(line_split, &String::from("Circle"), &String::from("fill color"))?;
-    return Ok(Box::new(Circle::new(center, radius, outline_color, fill_color)));
+    let fill_paint = parse_paint(line_split, &String::from("Circle"), &String::from("fill paint"))?;
+    let stroke = parse_stroke_attributes(line_split, &String::from("Circle"), common_attributes)?;
+    return Ok(Box::new(Circle::new(center, radius, outline_color, fill_paint, stroke)));
 }
 
-fn parse_triangle(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+fn parse_triangle(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
     let p0 = parse_point(line_split, &String::from("Triangle"), &String::from("first point"))?;
     let p1 = parse_point(line_split, &String::from("Triangle"), &String::from("second point"))?;
     let p2 = parse_point(line_split, &String::from("Triangle"), &String::from("third point"))?;
     let outline_color = parse_color(line_split, &String::from("Triangle"), &String::from("outline color"))?;
-    let fill_color = parse_color(line_split, &String::from("Triangle"), &String::from("fill color"))?;
-    return Ok(Box::new(Triangle::new(p0, p1, p2, outline_color, fill_color)));
+    let fill_paint = parse_paint(line_split, &String::from("Triangle"), &String::from("fill paint"))?;
+    let stroke = parse_stroke_attributes(line_split, &String::from("Triangle"), common_attributes)?;
+    return Ok(Box::new(Triangle::new(p0, p1, p2, outline_color, fill_paint, stroke)));
 }
 
-fn parse_diamond(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+fn parse_diamond(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
     let center = parse_point(line_split, &String::from("Diamond"), &String::from("center"))?;
     let horizontal_diagonal = parse_u32(line_split, &String::from("Diamond"), &String::from("horizontal diagonal"))?;
     let vertical_diagonal = parse_u32(line_split, &String::from("Diamond"), &String::from("vertical diagonal"))?;
     let outline_color = parse_color(line_split, &String::from("Diamond"), &String::from("outline color"))?;
-    let fill_color = parse_color(line_split, &String::from("Diamond"), &String::from("fill color"))?;
-    return Ok(Box::new(Diamond::new(center, horizontal_diagonal, vertical_diagonal, outline_color, fill_color)));
+    let fill_paint = parse_paint(line_split, &String::from("Diamond"), &String::from("fill paint"))?;
+    let stroke = parse_stroke_attributes(line_split, &String::from("Diamond"), common_attributes)?;
+    return Ok(Box::new(Diamond::new(center, horizontal_diagonal, vertical_diagonal, outline_color, fill_paint, stroke)));
 }
 
-fn parse_polygon(line_split: &mut SplitWhitespace) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+fn parse_polygon(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
     let points_number = parse_u32(line_split, &String::from("Polygon"), &String::from("number of points"))?;
 
-    let mut points: Vec<Point> = Vec::new();
-    points.reserve(points_number as usize);
+    let mut points: Vec<Point> = Vec::with_capacity(points_number as usize);
 
     for point_index in 0..points_number {
         let point = parse_point(line_split, &String::from("Polygon"), &format!("point {}", point_index))?;
         points.push(point);
     }
     let outline_color = parse_color(line_split, &String::from("Polygon"), &String::from("outline color"))?;
-    let fill_color = parse_color(line_split, &String::from("Polygon"), &String::from("fill color"))?;
-    return Ok(Box::new(Polygon::new(points, outline_color, fill_color)));
+    let fill_paint = parse_paint(line_split, &String::from("Polygon"), &String::from("fill paint"))?;
+
+    let mut fill_rule = FillRule::EvenOdd;
+    let mut stroke = Stroke::default();
+    parse_attributes(line_split, &String::from("Polygon"), common_attributes, |keyword, line_split| {
+        return Ok(parse_fill_rule_attribute(keyword, &mut fill_rule)
+                  || parse_stroke_attribute(keyword, line_split, &String::from("Polygon"), &mut stroke)?);
+    })?;
+    return Ok(Box::new(Polygon::new(points, outline_color, fill_paint, fill_rule, stroke)));
+}
+
+fn parse_ellipse(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let center = parse_point(line_split, &String::from("Ellipse"), &String::from("center"))?;
+    let horizontal_radius = parse_u32(line_split, &String::from("Ellipse"), &String::from("horizontal radius"))?;
+    let vertical_radius = parse_u32(line_split, &String::from("Ellipse"), &String::from("vertical radius"))?;
+    let outline_color = parse_color(line_split, &String::from("Ellipse"), &String::from("outline color"))?;
+    let fill_paint = parse_paint(line_split, &String::from("Ellipse"), &String::from("fill paint"))?;
+    let (rotation, stroke) = parse_rotation_and_stroke_attributes(line_split, &String::from("Ellipse"),
+                                                                  common_attributes)?;
+    return Ok(Box::new(Ellipse::new(center, horizontal_radius, vertical_radius, rotation,
+                                    outline_color, fill_paint, stroke)));
+}
+
+fn parse_arc(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let center = parse_point(line_split, &String::from("Arc"), &String::from("center"))?;
+    let horizontal_radius = parse_u32(line_split, &String::from("Arc"), &String::from("horizontal radius"))?;
+    let vertical_radius = parse_u32(line_split, &String::from("Arc"), &String::from("vertical radius"))?;
+    let start_angle = parse_f64(line_split, &String::from("Arc"), &String::from("start angle"))?;
+    let end_angle = parse_f64(line_split, &String::from("Arc"), &String::from("end angle"))?;
+    let color = parse_color(line_split, &String::from("Arc"), &String::from("color"))?;
+    let (rotation, stroke) = parse_rotation_and_stroke_attributes(line_split, &String::from("Arc"), common_attributes)?;
+    return Ok(Box::new(Arc::new(center, horizontal_radius, vertical_radius, start_angle, end_angle, rotation,
+                                color, stroke)));
+}
+
+fn parse_pie(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let center = parse_point(line_split, &String::from("Pie"), &String::from("center"))?;
+    let horizontal_radius = parse_u32(line_split, &String::from("Pie"), &String::from("horizontal radius"))?;
+    let vertical_radius = parse_u32(line_split, &String::from("Pie"), &String::from("vertical radius"))?;
+    let start_angle = parse_f64(line_split, &String::from("Pie"), &String::from("start angle"))?;
+    let end_angle = parse_f64(line_split, &String::from("Pie"), &String::from("end angle"))?;
+    let outline_color = parse_color(line_split, &String::from("Pie"), &String::from("outline color"))?;
+    let fill_paint = parse_paint(line_split, &String::from("Pie"), &String::from("fill paint"))?;
+    let (rotation, stroke) = parse_rotation_and_stroke_attributes(line_split, &String::from("Pie"), common_attributes)?;
+    return Ok(Box::new(Pie::new(center, horizontal_radius, vertical_radius, start_angle, end_angle, rotation,
+                                outline_color, fill_paint, stroke)));
+}
+
+fn parse_path(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let outline_color = parse_color(line_split, &String::from("Path"), &String::from("outline color"))?;
+    let fill_paint = parse_paint(line_split, &String::from("Path"), &String::from("fill paint"))?;
+
+    let mut commands: Vec<PathCommand> = Vec::new();
+    let mut fill_rule = FillRule::NonZero;
+    let mut stroke = Stroke::default();
+    parse_attributes(line_split, &String::from("Path"), common_attributes, |keyword, line_split| {
+        return Ok(parse_path_command(keyword, line_split, &String::from("Path"), &mut commands)?
+                  || parse_fill_rule_attribute(keyword, &mut fill_rule)
+                  || parse_stroke_attribute(keyword, line_split, &String::from("Path"), &mut stroke)?);
+    })?;
+
+    if commands.is_empty() {
+        return Err(create_parse_error(String::from("Path does not seem to have any command")));
+    }
+    return Ok(Box::new(Path::new(commands, outline_color, fill_paint, fill_rule, stroke)));
+}
+
+fn parse_regular_polygon(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let center = parse_point(line_split, &String::from("Regular polygon"), &String::from("center"))?;
+    let radius = parse_u32(line_split, &String::from("Regular polygon"), &String::from("radius"))?;
+    let sides = parse_u32(line_split, &String::from("Regular polygon"), &String::from("number of sides"))?;
+    if !(3..=MAX_INSCRIBED_VERTICES).contains(&sides) {
+        return Err(create_parse_error(format!("Regular polygon must have between 3 and {} sides, found {}",
+                                              MAX_INSCRIBED_VERTICES, sides)));
+    }
+    let outline_color = parse_color(line_split, &String::from("Regular polygon"), &String::from("outline color"))?;
+    let fill_paint = parse_paint(line_split, &String::from("Regular polygon"), &String::from("fill paint"))?;
+    let (rotation, stroke) = parse_rotation_and_stroke_attributes(line_split, &String::from("Regular polygon"),
+                                                                  common_attributes)?;
+    return Ok(Box::new(RegularPolygon::new(center, radius, sides, rotation, outline_color, fill_paint, stroke)));
+}
+
+fn parse_star(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let center = parse_point(line_split, &String::from("Star"), &String::from("center"))?;
+    let outer_radius = parse_u32(line_split, &String::from("Star"), &String::from("outer radius"))?;
+    let inner_radius = parse_u32(line_split, &String::from("Star"), &String::from("inner radius"))?;
+    let points = parse_u32(line_split, &String::from("Star"), &String::from("number of points"))?;
+    if !(2..=MAX_INSCRIBED_VERTICES / 2).contains(&points) {
+        return Err(create_parse_error(format!("Star must have between 2 and {} points, found {}",
+                                              MAX_INSCRIBED_VERTICES / 2, points)));
+    }
+    let outline_color = parse_color(line_split, &String::from("Star"), &String::from("outline color"))?;
+    let fill_paint = parse_paint(line_split, &String::from("Star"), &String::from("fill paint"))?;
+    let (rotation, stroke) = parse_rotation_and_stroke_attributes(line_split, &String::from("Star"),
+                                                                  common_attributes)?;
+    return Ok(Box::new(Star::new(center, outer_radius, inner_radius, points, rotation,
+                                 outline_color, fill_paint, stroke)));
+}
+
+fn parse_polyline(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let points_number = parse_u32(line_split, &String::from("Polyline"), &String::from("number of points"))?;
+    if points_number == 0 {
+        return Err(create_parse_error(String::from("Polyline must have at least one point")));
+    }
+
+    // The number of points comes from the input, so no memory is reserved for them beforehand
+    let mut points: Vec<Point> = Vec::new();
+
+    for point_index in 0..points_number {
+        let point = parse_point(line_split, &String::from("Polyline"), &format!("point {}", point_index))?;
+        points.push(point);
+    }
+    let color = parse_color(line_split, &String::from("Polyline"), &String::from("color"))?;
+    let stroke = parse_stroke_attributes(line_split, &String::from("Polyline"), common_attributes)?;
+    return Ok(Box::new(Polyline::new(points, color, stroke)));
+}
+
+fn parse_image(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let path = parse_path_string(line_split, &String::from("Image"))?;
+    let top_left = parse_point(line_split, &String::from("Image"), &String::from("top left"))?;
+
+    let mut width: Option<u32> = None;
+    let mut height: Option<u32> = None;
+    let parse_size = |line_split: &mut SplitWhitespace, attribute: &str| -> Result<u32, Box<dyn Error>> {
+        let size = parse_u32(line_split, &String::from("Image"), &String::from(attribute))?;
+        if size == 0 || size > MAX_IMAGE_SIZE {
+            return Err(create_parse_error(format!("Image {} must be between 1 and {}, found {}", attribute,
+                                                  MAX_IMAGE_SIZE, size)));
+        }
+        return Ok(size);
+    };
+    parse_attributes(line_split, &String::from("Image"), common_attributes, |keyword, line_split| {
+        if keyword == "WIDTH" {
+            width = Some(parse_size(line_split, "width")?);
+        } else if keyword == "HEIGHT" {
+            height = Some(parse_size(line_split, "height")?);
+        } else {
+            return Ok(false);
+        }
+        return Ok(true);
+    })?;
+    return Ok(Box::new(Image::new(path, top_left, width, height)));
+}
+
+fn parse_text<'a>(line: &'a str, line_split: &mut SplitWhitespace<'a>, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let position = parse_point(line_split, &String::from("Text"), &String::from("position"))?;
+    let size = parse_u32(line_split, &String::from("Text"), &String::from("size"))?;
+    if size == 0 {
+        return Err(create_parse_error(String::from("Text size must be positive")));
+    }
+    let color = parse_color(line_split, &String::from("Text"), &String::from("color"))?;
+    let content = parse_quoted_string(line, line_split, &String::from("Text"), &String::from("content"))?;
+
+    let mut alignment = TextAlignment::Left;
+    let mut vertical_alignment = VerticalAlignment::Top;
+    let mut font: Option<String> = None;
+    parse_attributes(line_split, &String::from("Text"), common_attributes, |keyword, line_split| {
+        if keyword == "ALIGN" {
+            alignment = match line_split.next() {
+                Some("LEFT") => TextAlignment::Left,
+                Some("CENTER") => TextAlignment::Center,
+                Some("RIGHT") => TextAlignment::Right,
+                other => {
+                    return Err(create_parse_error(format!("Text alignment must be LEFT, CENTER or RIGHT, found {}",
+                                                          other.unwrap_or("nothing"))));
+                }
+            };
+        } else if keyword == "VALIGN" {
+            vertical_alignment = match line_split.next() {
+                Some("TOP") => VerticalAlignment::Top,
+                Some("MIDDLE") => VerticalAlignment::Middle,
+                Some("BASELINE") => VerticalAlignment::Baseline,
+                Some("BOTTOM") => VerticalAlignment::Bottom,
+                other => {
+                    return Err(create_parse_error(format!("Text vertical alignment must be TOP, MIDDLE, BASELINE or \
+                                                           BOTTOM, found {}", other.unwrap_or("nothing"))));
+                }
+            };
+        } else if keyword == "FONT" {
+            font = Some(parse_path_string(line_split, &String::from("Text font"))?);
+        } else {
+            return Ok(false);
+        }
+        return Ok(true);
+    })?;
+    return Ok(Box::new(Text::new(position, size, color, content, alignment, vertical_alignment, font)));
+}
+
+fn parse_group(line_split: &mut SplitWhitespace, shapes: Vec<Box<dyn Shape>>, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let mut opacity = 100;
+    parse_attributes(line_split, &String::from("Group"), common_attributes, |keyword, line_split| {
+        if keyword != "OPACITY" {
+            return Ok(false);
+        }
+        opacity = parse_u8(line_split, &String::from("Group"), &String::from("opacity"))?;
+        return Ok(true);
+    })?;
+    return Ok(Box::new(Group::new(shapes, opacity)));
+}
+
+fn parse_clip(line_split: &mut SplitWhitespace, common_attributes: &mut CommonAttributes)
+-> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let region = match line_split.next() {
+        Some("RECTANGLE") => {
+            let top_left = parse_point(line_split, &String::from("Clip rectangle"), &String::from("top left"))?;
+            let height = parse_u32(line_split, &String::from("Clip rectangle"), &String::from("height"))?;
+            let width = parse_u32(line_split, &String::from("Clip rectangle"), &String::from("width"))?;
+            ClipRegion::Rectangle { top_left: top_left, height: height, width: width }
+        },
+        Some("CIRCLE") => {
+            let center = parse_point(line_split, &String::from("Clip circle"), &String::from("center"))?;
+            let radius = parse_u32(line_split, &String::from("Clip circle"), &String::from("radius"))?;
+            ClipRegion::Circle { center: center, radius: radius }
+        },
+        Some("POLYGON") => {
+            let points_number = parse_u32(line_split, &String::from("Clip polygon"),
+                                          &String::from("number of points"))?;
+            let mut points: Vec<Point> = Vec::new();
+            for point_index in 0..points_number {
+                points.push(parse_point(line_split, &String::from("Clip polygon"), &format!("point {}", point_index))?);
+            }
+            ClipRegion::Polygon(points)
+        },
+        other => {
+            return Err(create_parse_error(format!("Clip region must be RECTANGLE, CIRCLE or POLYGON, found {}",
+                                                  other.unwrap_or("nothing"))));
+        }
+    };
+
+    parse_attributes(line_split, &String::from("Clip"), common_attributes, |_, _| Ok(false))?;
+    if !common_attributes.effects.is_empty() {
+        return Err(create_parse_error(String::from("Clip does not support effects")));
+    }
+    if common_attributes.blend_mode != BlendMode::Normal {
+        return Err(create_parse_error(String::from("Clip does not support blend modes")));
+    }
+    return Ok(Box::new(Clip::new(region)));
+}
+
+fn parse_soft_mask(line_split: &mut SplitWhitespace, shapes: Vec<Box<dyn Shape>>,
+                   common_attributes: &mut CommonAttributes) -> Result<Box<dyn Shape>, Box<dyn Error>> {
+    let mode = match line_split.next() {
+        Some("ALPHA") => MaskMode::Alpha,
+        Some("LUMINANCE") => MaskMode::Luminance,
+        other => {
+            return Err(create_parse_error(format!("Mask mode must be ALPHA or LUMINANCE, found {}",
+                                                  other.unwrap_or("nothing"))));
+        }
+    };
+
+    parse_attributes(line_split, &String::from("Mask"), common_attributes, |_, _| Ok(false))?;
+    if !common_attributes.effects.is_empty() {
+        return Err(create_parse_error(String::from("Mask does not support effects")));
+    }
+    if common_attributes.blend_mode != BlendMode::Normal {
+        return Err(create_parse_error(String::from("Mask does not support blend modes")));
+    }
+    return Ok(Box::new(SoftMask::new(shapes, mode)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parses the transform attributes of a line, the same way parse_attributes does
+    fn parse_transforms(line: &str) -> Result<Transform, Box<dyn Error>> {
+        let mut line_split = line.split_whitespace();
+        let mut transform = Transform::identity();
+        while let Some(keyword) = line_split.next() {
+            if !parse_transform_attribute(keyword, &mut line_split, &String::from("Shape"), &mut transform)? {
+                return Err(create_parse_error(format!("Unknown attribute {}", keyword)));
+            }
+        }
+        return Ok(transform);
+    }
+
+    fn assert_maps(transform: &Transform, from: (f64, f64), to: (f64, f64)) {
+        let point = transform.apply(FloatPoint::new(from.0, from.1));
+        assert!((point.x - to.0).abs() < 1e-9 && (point.y - to.1).abs() < 1e-9,
+                "({}, {}) is mapped to ({}, {}) instead of ({}, {})", from.0, from.1, point.x, point.y, to.0, to.1);
+    }
+
+    #[test]
+    fn parses_translations() {
+        let transform = parse_transforms("TRANSLATE 10 -5").unwrap();
+        assert_maps(&transform, (1.0, 2.0), (11.0, -3.0));
+    }
+
+    #[test]
+    fn parses_rotations_around_the_origin_or_a_center() {
+        assert_maps(&parse_transforms("ROTATE 90").unwrap(), (10.0, 0.0), (0.0, 10.0));
+        assert_maps(&parse_transforms("ROTATE 90 10 10").unwrap(), (20.0, 10.0), (10.0, 20.0));
+    }
+
+    #[test]
+    fn tells_scale_factors_and_center_apart_by_the_number_of_values() {
+        assert_maps(&parse_transforms("SCALE 2").unwrap(), (3.0, 4.0), (6.0, 8.0));
+        assert_maps(&parse_transforms("SCALE 2 3").unwrap(), (3.0, 4.0), (6.0, 12.0));
+        assert_maps(&parse_transforms("SCALE 2 50 50").unwrap(), (60.0, 40.0), (70.0, 30.0));
+        assert_maps(&parse_transforms("SCALE 2 3 50 50").unwrap(), (60.0, 40.0), (70.0, 20.0));
+        assert_maps(&parse_transforms("SCALE 2 TRANSLATE 1 1").unwrap(), (3.0, 4.0), (7.0, 9.0));
+    }
+
+    #[test]
+    fn parses_skews() {
+        assert_maps(&parse_transforms("SKEW 45 0").unwrap(), (0.0, 10.0), (10.0, 10.0));
+        assert_maps(&parse_transforms("SKEW 0 45").unwrap(), (10.0, 0.0), (10.0, 10.0));
+    }
+
+    #[test]
+    fn applies_transforms_in_the_order_they_are_written() {
+        assert_maps(&parse_transforms("TRANSLATE 10 0 ROTATE 90").unwrap(), (0.0, 0.0), (0.0, 10.0));
+        assert_maps(&parse_transforms("ROTATE 90 TRANSLATE 10 0").unwrap(), (0.0, 0.0), (10.0, 0.0));
+    }
+
+    #[test]
+    fn rejects_transforms_scaling_to_nothing_or_infinity() {
+        assert!(parse_transforms("SCALE 0").is_err());
+        assert!(parse_transforms("SCALE 1 0 5 5").is_err());
+        assert!(parse_transforms("SCALE 1e200 SCALE 1e200").is_err());
+        assert!(parse_transforms("SKEW 45 45").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_values() {
+        assert!(parse_transforms("TRANSLATE 10").is_err());
+        assert!(parse_transforms("ROTATE").is_err());
+        assert!(parse_transforms("SCALE").is_err());
+        assert!(parse_transforms("SKEW 10 X").is_err());
+    }
 }
@@ -1,4 +1,5 @@
 
+#[allow(clippy::module_inception)]
 pub mod shape_visitor;
 pub mod print_shape_visitor;
 pub mod draw_shape_visitor;
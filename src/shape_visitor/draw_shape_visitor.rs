@@ -1,33 +1,84 @@
 
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
 
-use crate::shapes::{canvas::Canvas,
+use crate::shapes::{shape::Shape,
+                    canvas::Canvas,
                     line::Line,
                     triangle::Triangle,
                     square::Square,
                     rectangle::Rectangle,
                     diamond::Diamond,
                     polygon::Polygon,
-                    circle::Circle};
+                    circle::Circle,
+                    ellipse::Ellipse,
+                    arc::Arc,
+                    pie::Pie,
+                    path::Path,
+                    regular_polygon::RegularPolygon,
+                    star::Star,
+                    polyline::Polyline,
+                    image::{Image, MAX_IMAGE_SIZE},
+                    text::Text,
+                    transformed_shape::TransformedShape,
+                    group::Group,
+                    clip::{Clip, ClipRegion},
+                    soft_mask::{SoftMask, MaskMode},
+                    blended_shape::BlendedShape,
+                    filtered_shape::FilteredShape,
+                    layered_shape::LayeredShape};
 
-use crate::utils::{ generic_error::GenericError, color::Color, point::Point };
+use crate::utils::{ generic_error::GenericError, color::Color, point::Point, fill_rule::FillRule,
+                    float_point::FloatPoint, stroke::Stroke, paint::{ Paint, Pattern },
+                    transform::Transform, blend_mode::BlendMode, effect::Effect };
+use crate::rasterizer::{ scanline::{ Span, get_polygon_spans, add_polygon_coverage },
+                         mask::Mask,
+                         antialiasing::{ add_line_coverage, add_circle_coverage },
+                         flattening::{ TOLERANCE, get_circle_points, get_ellipse_points, get_elliptical_arc_points,
+                                       get_path_polylines, get_rounded_rectangle_points,
+                                       get_inscribed_polygon_points },
+                         stroke::{ get_polyline_stroke_contours, get_circle_stroke_contours },
+                         dash::{ get_dashes, MAX_DASHES },
+                         text_layout::get_text_contours,
+                         bitmap_font::BitmapFont,
+                         truetype_font::TrueTypeFont,
+                         blur::blur_image,
+                         render_mode::RenderMode };
 
 use std::string::String;
 use std::error::Error;
 use std::vec::Vec;
-use std::collections::VecDeque;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 extern crate image;
-use image::{RgbaImage, Rgba};
+use image::{RgbaImage, Rgba, imageops::FilterType};
 
 pub struct DrawShapeVisitor {
-    image: RgbaImage
+    image: RgbaImage,
+    render_mode: RenderMode,
+    // Transform of the shape being drawn, composed with the transforms of the shapes wrapping it
+    transform: Transform,
+    // Region outside of which nothing is drawn, none when drawing is not restricted
+    clip: Option<Mask>,
+    blend_mode: BlendMode,
+    // Files used by images, textures and texts, loaded once
+    loaded_images: HashMap<String, Rc<RgbaImage>>,
+    loaded_fonts: HashMap<String, Rc<Vec<u8>>>
 }
 
 impl DrawShapeVisitor {
-    pub fn new() -> Self {
-        return DrawShapeVisitor{image: RgbaImage::new(0, 0)};
+    pub fn new(render_mode: RenderMode) -> Self {
+        return DrawShapeVisitor{image: RgbaImage::new(0, 0), render_mode: render_mode,
+                                transform: Transform::identity(), clip: None,
+                                blend_mode: BlendMode::Normal, loaded_images: HashMap::new(),
+                                loaded_fonts: HashMap::new()};
     }
+
+    // Clipping regions and soft masks drawn so far stop applying, like at the end of a group
+    pub fn reset_clip(&mut self) {
+        self.clip = None;
+    }
+
     pub fn write_image(&self, file_name: &String) -> Result<(), Box<dyn Error>> {
         return match self.image.save(file_name) {
             Ok(_) => Ok(()),
@@ -39,48 +90,126 @@ impl DrawShapeVisitor {
     }
 }
 
+impl DrawShapeVisitor {
+    fn load_image(&mut self, path: &String) -> Result<Rc<RgbaImage>, Box<dyn Error>> {
+        if let Some(loaded_image) = self.loaded_images.get(path) {
+            return Ok(Rc::clone(loaded_image));
+        }
+
+        let loaded_image = match image::open(path) {
+            Ok(dynamic_image) => Rc::new(dynamic_image.to_rgba8()),
+            Err(err) => {
+                let new_error_string = format!("Cannot open image {}: {}", path, err);
+                return Err(Box::new(GenericError::new(new_error_string)));
+            }
+        };
+        self.loaded_images.insert(path.clone(), Rc::clone(&loaded_image));
+        return Ok(loaded_image);
+    }
+
+    fn load_font(&mut self, path: &String) -> Result<Rc<Vec<u8>>, Box<dyn Error>> {
+        if let Some(loaded_font) = self.loaded_fonts.get(path) {
+            return Ok(Rc::clone(loaded_font));
+        }
+
+        let loaded_font = match std::fs::read(path) {
+            Ok(data) => Rc::new(data),
+            Err(err) => {
+                let new_error_string = format!("Cannot open font {}: {}", path, err);
+                return Err(Box::new(GenericError::new(new_error_string)));
+            }
+        };
+        self.loaded_fonts.insert(path.clone(), Rc::clone(&loaded_font));
+        return Ok(loaded_font);
+    }
+
+    // Draws the shapes into a transparent layer of the size of the canvas instead of the image. Clipping regions,
+    // soft masks and blend modes set before apply to the whole layer once it is composited, the clipping regions
+    // and soft masks set by the shapes last until the end of the layer.
+    fn draw_layer_shapes<'a, I>(&mut self, shapes: I) -> Result<RgbaImage, Box<dyn Error>>
+    where I: IntoIterator<Item = &'a dyn Shape> {
+        let layer = RgbaImage::new(self.image.width(), self.image.height());
+        let parent_image = std::mem::replace(&mut self.image, layer);
+        let parent_clip = self.clip.take();
+        let parent_blend_mode = std::mem::replace(&mut self.blend_mode, BlendMode::Normal);
+
+        let mut result = Ok(());
+        for shape in shapes {
+            result = shape.accept(self);
+            if result.is_err() {
+                break;
+            }
+        }
+
+        let layer = std::mem::replace(&mut self.image, parent_image);
+        self.clip = parent_clip;
+        self.blend_mode = parent_blend_mode;
+        result?;
+        return Ok(layer);
+    }
+
+    fn draw_paint_mask(&mut self, mask: &Mask, paint: &Paint) -> Result<(), Box<dyn Error>> {
+        let texture = match paint {
+            Paint::Texture { path, .. } => Some(self.load_image(path)?),
+            _ => None
+        };
+
+        // Gradients and textures are laid out in the coordinates of the shape, before it is transformed.
+        // Patterns stay in the coordinates of the canvas, so that they line up across shapes.
+        let inverse_transform = match paint {
+            Paint::Pattern { .. } => Transform::identity(),
+            _ => self.transform.inverse().unwrap_or_else(Transform::identity)
+        };
+        match paint {
+            Paint::Solid(color) => {
+                draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, mask, Rgba(color_to_rgba(color)));
+            },
+            _ => draw_mask_with(&mut self.image, self.clip.as_ref(), self.blend_mode, mask, |x, y| {
+                let point = inverse_transform.apply(FloatPoint::new(x as f64, y as f64));
+                return get_paint_color(paint, texture.as_deref(), point);
+            })
+        };
+        return Ok(());
+    }
+
+    // Draws the shape bounded by the contour, its fill and then its outline
+    fn draw_closed_contour(&mut self, contour: Vec<FloatPoint>, fill_rule: FillRule, stroke: &Stroke,
+                           outline_color: Color, fill_paint: &Paint) -> Result<(), Box<dyn Error>> {
+        let outline_color = Rgba(color_to_rgba(&outline_color));
+
+        let outline_mask = get_outline_mask(&self.image, &contour, true, stroke, self.render_mode,
+                                            &self.transform)?;
+        let mut fill_mask = get_contours_mask(&self.image, &[contour], fill_rule, self.render_mode,
+                                              &self.transform);
+
+        if self.render_mode == RenderMode::Aliased {
+            fill_mask.subtract(&outline_mask);
+        }
+
+        self.draw_paint_mask(&fill_mask, fill_paint)?;
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &outline_mask, outline_color);
+        return Ok(());
+    }
+}
+
 impl ShapeVisitor for DrawShapeVisitor {
     fn visit_canvas(&mut self, canvas: &Canvas) -> Result<(), Box<dyn Error>> {
         self.image = RgbaImage::new(canvas.get_width(), canvas.get_height());
         let fill_color = Rgba(color_to_rgba(&canvas.get_color()));
-        
+
         for pixel in self.image.pixels_mut() {
-            *pixel = fill_color;
+            *pixel = blend_source_over(*pixel, fill_color);
         }
         return Ok(());
     }
 
     fn visit_line(&mut self, line: &Line) -> Result<(), Box<dyn Error>> {
         let color = Rgba(color_to_rgba(&line.get_color()));
-        // Generalized Integer Bresenham's Algorithm for all quadrants
-        // Stolen from https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
-        let mut x0 = line.get_p0().x;
-        let mut y0 = line.get_p0().y;
-        let x1 = line.get_p1().x;
-        let y1 = line.get_p1().y;
-
-        let dx = (x1 - x0).abs();
-        let sx = if x0 < x1 {1} else {-1};
-        let dy = -(y1 - y0).abs();
-        let sy = if y0 < y1 {1} else {-1};
-        let mut err = dx + dy;
-
-        loop {
-            put_pixel(&mut self.image, x0, y0, color);
-            if (x0 == x1) && (y0 == y1) {
-                break;
-            }
+        let points = [FloatPoint::from(line.get_p0()), FloatPoint::from(line.get_p1())];
+        let mask = get_outline_mask(&self.image, &points, false, line.get_stroke(), self.render_mode,
+                                    &self.transform)?;
 
-            let e2 = 2 * err;
-            if e2 >= dy {
-                err += dy;
-                x0 += sx;
-            }
-            if e2 <= dx {
-                err += dx;
-                y0 += sy;
-            }
-        }
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &mask, color);
         return Ok(());
     }
 
@@ -88,35 +217,74 @@ impl ShapeVisitor for DrawShapeVisitor {
         let p0 = triangle.get_p0();
         let p1 = triangle.get_p1();
         let p2 = triangle.get_p2();
-        let polygon = Polygon::new(Vec::from([p0, p1, p2]), triangle.get_outline_color(), triangle.get_fill_color());
+        let polygon = Polygon::new(Vec::from([p0, p1, p2]), triangle.get_outline_color(), triangle.get_fill_paint().clone(),
+                                   FillRule::NonZero, triangle.get_stroke().clone());
         return self.visit_polygon(&polygon);
     }
 
     fn visit_square(&mut self, square: &Square) -> Result<(), Box<dyn Error>> {
         let rectangle = Rectangle::new(square.get_top_left(), square.get_side(), square.get_side(),
-                                       square.get_outline_color(), square.get_fill_color());
+                                       square.get_outline_color(), square.get_fill_paint().clone(),
+                                       square.get_corner_radii(), square.get_stroke().clone());
         return self.visit_rectangle(&rectangle);
     }
 
     fn visit_rectangle(&mut self, rectangle: &Rectangle) -> Result<(), Box<dyn Error>> {
         let outline_color = Rgba(color_to_rgba(&rectangle.get_outline_color()));
-        let fill_color = Rgba(color_to_rgba(&rectangle.get_fill_color()));
+
+        let top_left = rectangle.get_top_left();
+        let bottom_right = Point::new(top_left.x + rectangle.get_width() as i32 - 1,
+                                      top_left.y + rectangle.get_height() as i32 - 1);
+
+        // Rounded corners go through the polygon rasterizer, the outline runs through the centers of the
+        // border pixels like the outline of a sharp rectangle
+        let corner_radii = rectangle.get_corner_radii();
+        if !corner_radii.is_zero() && rectangle.get_width() > 0 && rectangle.get_height() > 0 {
+            let contour = get_rounded_rectangle_points(FloatPoint::from(top_left), FloatPoint::from(bottom_right),
+                                                       &corner_radii, get_flattening_tolerance(&self.transform));
+            self.draw_closed_contour(contour, FillRule::NonZero, rectangle.get_stroke(),
+                                     rectangle.get_outline_color(), rectangle.get_fill_paint())?;
+            return Ok(());
+        }
+
+        // Once transformed the rectangle is no longer aligned with the pixel grid
+        if !self.transform.is_identity() && rectangle.get_width() > 0 && rectangle.get_height() > 0 {
+            let corners = [top_left, Point::new(bottom_right.x, top_left.y),
+                           bottom_right, Point::new(top_left.x, bottom_right.y)];
+            let contour: Vec<FloatPoint> = corners.iter().map(|point| FloatPoint::from(*point)).collect();
+            self.draw_closed_contour(contour, FillRule::NonZero, rectangle.get_stroke(),
+                                     rectangle.get_outline_color(), rectangle.get_fill_paint())?;
+            return Ok(());
+        }
+
+        let mut outline_mask = Mask::new(self.image.width(), self.image.height());
+        let mut fill_mask = Mask::new(self.image.width(), self.image.height());
 
         for x in 0..rectangle.get_width() {
             for y in 0..rectangle.get_height() {
-                let x_coord = rectangle.get_top_left().x + (x as i32);
-                let y_coord = rectangle.get_top_left().y + (y as i32);
+                let x_coord = top_left.x + (x as i32);
+                let y_coord = top_left.y + (y as i32);
 
-                let color: Rgba<u8>;
                 if (x == 0) || (x == rectangle.get_width() - 1) || (y == 0) || (y == rectangle.get_height() - 1) {
-                    color = outline_color;
+                    outline_mask.add_coverage(x_coord, y_coord, 1.0);
                 } else {
-                    color = fill_color;
+                    fill_mask.add_coverage(x_coord, y_coord, 1.0);
                 }
-
-                put_pixel(&mut self.image, x_coord, y_coord, color);
             }
         }
+
+        let stroke = rectangle.get_stroke();
+        if (stroke.get_width() > 1 || stroke.is_dashed()) && rectangle.get_width() > 0 && rectangle.get_height() > 0 {
+            let corners = [top_left, Point::new(bottom_right.x, top_left.y),
+                           bottom_right, Point::new(top_left.x, bottom_right.y)];
+            let corners: Vec<FloatPoint> = corners.iter().map(|point| FloatPoint::from(*point)).collect();
+            outline_mask = get_outline_mask(&self.image, &corners, true, stroke, self.render_mode,
+                                            &self.transform)?;
+            fill_mask.subtract(&outline_mask);
+        }
+
+        self.draw_paint_mask(&fill_mask, rectangle.get_fill_paint())?;
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &outline_mask, outline_color);
         return Ok(());
     }
 
@@ -129,135 +297,887 @@ impl ShapeVisitor for DrawShapeVisitor {
         p2.x += (diamond.get_horizontal_diagonal() / 2) as i32;
         let mut p3 = diamond.get_center();
         p3.y += (diamond.get_vertical_diagonal() / 2) as i32;
-        let polygon = Polygon::new(Vec::from([p0, p1, p2, p3]), diamond.get_outline_color(), diamond.get_fill_color());
+        let polygon = Polygon::new(Vec::from([p0, p1, p2, p3]), diamond.get_outline_color(), diamond.get_fill_paint().clone(),
+                                   FillRule::NonZero, diamond.get_stroke().clone());
         return self.visit_polygon(&polygon);
     }
 
     fn visit_polygon(&mut self, polygon: &Polygon) -> Result<(), Box<dyn Error>> {
-        let outline_color = Rgba(color_to_rgba(&polygon.get_outline_color()));
-        let fill_color = Rgba(color_to_rgba(&polygon.get_fill_color()));
-        let points = polygon.get_points();
-        let center = get_polygon_center(points);
-
-        draw_polygon_outline(points, polygon.get_outline_color(), self)?;
-        return flood_fill(&mut self.image, center.x, center.y, outline_color, fill_color);
+        let contour: Vec<FloatPoint> = polygon.get_points().iter().map(|point| FloatPoint::from(*point)).collect();
+        self.draw_closed_contour(contour, polygon.get_fill_rule(), polygon.get_stroke(),
+                                 polygon.get_outline_color(), polygon.get_fill_paint())?;
+        return Ok(());
     }
 
     fn visit_circle(&mut self, circle: &Circle) -> Result<(), Box<dyn Error>> {
         let outline_color = Rgba(color_to_rgba(&circle.get_outline_color()));
-        let fill_color = Rgba(color_to_rgba(&circle.get_fill_color()));
-        // Circle generation using Brasenham's algorithm
-        // https://www.geeksforgeeks.org/bresenhams-circle-drawing-algorithm/
-        let xc = circle.get_center().x;
-        let yc = circle.get_center().y;
-        let mut x: i32 = 0;
-        let mut y: i32 = circle.get_radius() as i32;
-        let mut d: i32 = 3 - 2 * (circle.get_radius() as i32);
-
-        while y >= x {
-            put_pixels_in_8_parts_around_circle(&mut self.image, xc, yc, x, y, outline_color);
-            x += 1;
-            if d > 0 {
-                y -= 1;
-                d += 4 * (x - y) + 10;
-            } else {
-                d += 4 * x + 6;
+
+        let center = circle.get_center();
+        let radius = circle.get_radius();
+        let stroke = circle.get_stroke();
+
+        // Once transformed the circle may become an ellipse, drawn as a polygon
+        if !self.transform.is_identity() {
+            let contour = get_circle_points(FloatPoint::from(center), radius as f64,
+                                            get_flattening_tolerance(&self.transform));
+            self.draw_closed_contour(contour, FillRule::NonZero, stroke,
+                                     circle.get_outline_color(), circle.get_fill_paint())?;
+            return Ok(());
+        }
+
+        let mut outline_mask = Mask::new(self.image.width(), self.image.height());
+        let mut fill_mask = Mask::new(self.image.width(), self.image.height());
+
+        match self.render_mode {
+            RenderMode::Aliased => {
+                let outline = get_circle_outline_points(center, radius);
+                for point in &outline {
+                    outline_mask.add_coverage(point.x, point.y, 1.0);
+                }
+                for span in get_circle_spans(&outline, center, radius) {
+                    fill_mask.add_span(&span);
+                }
+            },
+            RenderMode::AntiAliased => {
+                add_circle_coverage(&mut outline_mask, &mut fill_mask, center, radius);
             }
+        };
+
+        if stroke.is_dashed() {
+            let perimeter = get_circle_points(FloatPoint::from(center), radius as f64, TOLERANCE);
+            outline_mask = get_outline_mask(&self.image, &perimeter, true, stroke, self.render_mode,
+                                            &self.transform)?;
+        } else if stroke.get_width() > 1 {
+            let contours = get_circle_stroke_contours(FloatPoint::from(center), radius as f64, stroke, TOLERANCE);
+            outline_mask = get_contours_mask(&self.image, &contours, FillRule::NonZero, self.render_mode,
+                                             &self.transform);
+        }
+        if stroke.get_width() > 1 && self.render_mode == RenderMode::AntiAliased {
+            let disk = get_circle_points(FloatPoint::from(center), radius as f64, TOLERANCE);
+            fill_mask = get_contours_mask(&self.image, &[disk], FillRule::NonZero, self.render_mode,
+                                          &self.transform);
+        }
+        if self.render_mode == RenderMode::Aliased {
+            fill_mask.subtract(&outline_mask);
+        }
+
+        self.draw_paint_mask(&fill_mask, circle.get_fill_paint())?;
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &outline_mask, outline_color);
+        return Ok(());
+    }
+
+    fn visit_ellipse(&mut self, ellipse: &Ellipse) -> Result<(), Box<dyn Error>> {
+        let contour = get_ellipse_points(FloatPoint::from(ellipse.get_center()),
+                                         ellipse.get_horizontal_radius() as f64, ellipse.get_vertical_radius() as f64,
+                                         ellipse.get_rotation().to_radians(),
+                                         get_flattening_tolerance(&self.transform));
+        self.draw_closed_contour(contour, FillRule::NonZero, ellipse.get_stroke(),
+                                 ellipse.get_outline_color(), ellipse.get_fill_paint())?;
+        return Ok(());
+    }
+
+    fn visit_arc(&mut self, arc: &Arc) -> Result<(), Box<dyn Error>> {
+        let color = Rgba(color_to_rgba(&arc.get_color()));
+        let points = get_elliptical_arc_points(FloatPoint::from(arc.get_center()),
+                                               arc.get_horizontal_radius() as f64, arc.get_vertical_radius() as f64,
+                                               arc.get_rotation().to_radians(), arc.get_start_angle().to_radians(),
+                                               (arc.get_end_angle() - arc.get_start_angle()).to_radians(),
+                                               get_flattening_tolerance(&self.transform));
+        let mask = get_outline_mask(&self.image, &points, false, arc.get_stroke(), self.render_mode,
+                                    &self.transform)?;
+
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &mask, color);
+        return Ok(());
+    }
+
+    fn visit_pie(&mut self, pie: &Pie) -> Result<(), Box<dyn Error>> {
+        let center = FloatPoint::from(pie.get_center());
+        let mut contour = Vec::from([center]);
+        contour.extend(get_elliptical_arc_points(center,
+                                                 pie.get_horizontal_radius() as f64, pie.get_vertical_radius() as f64,
+                                                 pie.get_rotation().to_radians(), pie.get_start_angle().to_radians(),
+                                                 (pie.get_end_angle() - pie.get_start_angle()).to_radians(),
+                                                 get_flattening_tolerance(&self.transform)));
+        self.draw_closed_contour(contour, FillRule::NonZero, pie.get_stroke(),
+                                 pie.get_outline_color(), pie.get_fill_paint())?;
+        return Ok(());
+    }
+
+    fn visit_path(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let outline_color = Rgba(color_to_rgba(&path.get_outline_color()));
+
+        // Open subpaths are filled as if they were closed, like in SVG
+        let polylines = get_path_polylines(path.get_commands(), get_flattening_tolerance(&self.transform));
+        let mut outline_mask = Mask::new(self.image.width(), self.image.height());
+        for (points, is_closed) in &polylines {
+            outline_mask.add_mask(&get_outline_mask(&self.image, points, *is_closed, path.get_stroke(),
+                                                    self.render_mode, &self.transform)?);
         }
-        return flood_fill(&mut self.image, xc, yc, outline_color, fill_color);
+        let contours: Vec<Vec<FloatPoint>> = polylines.into_iter().map(|(points, _)| points).collect();
+        let mut fill_mask = get_contours_mask(&self.image, &contours, path.get_fill_rule(), self.render_mode,
+                                              &self.transform);
+
+        if self.render_mode == RenderMode::Aliased {
+            fill_mask.subtract(&outline_mask);
+        }
+
+        self.draw_paint_mask(&fill_mask, path.get_fill_paint())?;
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &outline_mask, outline_color);
+        return Ok(());
+    }
+
+    fn visit_regular_polygon(&mut self, regular_polygon: &RegularPolygon) -> Result<(), Box<dyn Error>> {
+        let contour = get_inscribed_polygon_points(FloatPoint::from(regular_polygon.get_center()),
+                                                   &[regular_polygon.get_radius() as f64],
+                                                   regular_polygon.get_sides(),
+                                                   regular_polygon.get_rotation().to_radians());
+        self.draw_closed_contour(contour, FillRule::NonZero, regular_polygon.get_stroke(),
+                                 regular_polygon.get_outline_color(), regular_polygon.get_fill_paint())?;
+        return Ok(());
+    }
+
+    fn visit_star(&mut self, star: &Star) -> Result<(), Box<dyn Error>> {
+        // Every point has an outer and an inner vertex
+        let vertices_number = match star.get_points().checked_mul(2) {
+            None => return Err(Box::new(GenericError::new(format!("Star has too many points: {}",
+                                                                  star.get_points())))),
+            Some(vertices_number) => vertices_number
+        };
+        let contour = get_inscribed_polygon_points(FloatPoint::from(star.get_center()),
+                                                   &[star.get_outer_radius() as f64, star.get_inner_radius() as f64],
+                                                   vertices_number, star.get_rotation().to_radians());
+        self.draw_closed_contour(contour, FillRule::NonZero, star.get_stroke(),
+                                 star.get_outline_color(), star.get_fill_paint())?;
+        return Ok(());
+    }
+
+    fn visit_image(&mut self, image: &Image) -> Result<(), Box<dyn Error>> {
+        let source = self.load_image(image.get_path())?;
+        if source.width() == 0 || source.height() == 0 {
+            return Ok(());
+        }
+
+        let scale_size = |size: u32, from: u32, to: u32| ((size as f64) * (to as f64) / (from as f64)).round() as u32;
+        let (width, height) = match (image.get_width(), image.get_height()) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) => (width, scale_size(source.height(), source.width(), width)),
+            (None, Some(height)) => (scale_size(source.width(), source.height(), height), height),
+            (None, None) => (source.width(), source.height())
+        };
+        // The size kept from the file can be too large or round down to nothing
+        if width == 0 || height == 0 || width > MAX_IMAGE_SIZE || height > MAX_IMAGE_SIZE {
+            let error_string = format!("Image {} would be drawn {}x{}, sizes must be between 1 and {}",
+                                       image.get_path(), width, height, MAX_IMAGE_SIZE);
+            return Err(Box::new(GenericError::new(error_string)));
+        }
+
+        // Only the part of an image that is not transformed inside the canvas is drawn
+        let top_left = image.get_top_left();
+        let x_start = std::cmp::max(top_left.x as i64, 0);
+        let x_end = std::cmp::min(top_left.x as i64 + width as i64, self.image.width() as i64);
+        let y_start = std::cmp::max(top_left.y as i64, 0);
+        let y_end = std::cmp::min(top_left.y as i64 + height as i64, self.image.height() as i64);
+        if self.transform.is_identity() && (x_start >= x_end || y_start >= y_end) {
+            return Ok(());
+        }
+
+        let scaled_image;
+        let pixels: &RgbaImage = if (width, height) == source.dimensions() {
+            &source
+        } else {
+            let filter = match self.render_mode {
+                RenderMode::Aliased => FilterType::Nearest,
+                RenderMode::AntiAliased => FilterType::Triangle
+            };
+            scaled_image = image::imageops::resize(source.as_ref(), width, height, filter);
+            &scaled_image
+        };
+
+        // Pixels of a transformed image are sampled at the position they come from, the edges of the image
+        // lie half a pixel away from the centers of its border pixels
+        if !self.transform.is_identity() {
+            let left = top_left.x as f64 - 0.5;
+            let top = top_left.y as f64 - 0.5;
+            let right = left + width as f64;
+            let bottom = top + height as f64;
+            let contour = Vec::from([FloatPoint::new(left, top), FloatPoint::new(right, top),
+                                     FloatPoint::new(right, bottom), FloatPoint::new(left, bottom)]);
+            let mask = get_contours_mask(&self.image, &[contour], FillRule::NonZero, self.render_mode,
+                                         &self.transform);
+            let inverse_transform = match self.transform.inverse() {
+                None => return Ok(()),
+                Some(inverse_transform) => inverse_transform
+            };
+
+            draw_mask_with(&mut self.image, self.clip.as_ref(), self.blend_mode, &mask, |x, y| {
+                let point = inverse_transform.apply(FloatPoint::new(x as f64, y as f64));
+                let source_x = (point.x - left).floor().clamp(0.0, (width - 1) as f64) as u32;
+                let source_y = (point.y - top).floor().clamp(0.0, (height - 1) as f64) as u32;
+                return *pixels.get_pixel(source_x, source_y);
+            });
+            return Ok(());
+        }
+
+        let mut mask = Mask::new(self.image.width(), self.image.height());
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                mask.add_coverage(x as i32, y as i32, 1.0);
+            }
+        }
+
+        draw_mask_with(&mut self.image, self.clip.as_ref(), self.blend_mode, &mask, |x, y| {
+            return *pixels.get_pixel((x - top_left.x) as u32, (y - top_left.y) as u32);
+        });
+        return Ok(());
+    }
+
+    fn visit_text(&mut self, text: &Text) -> Result<(), Box<dyn Error>> {
+        let color = Rgba(color_to_rgba(&text.get_color()));
+        let position = FloatPoint::from(text.get_position());
+        let size = text.get_size() as f64;
+
+        // Glyphs of font files are designed to be drawn anti-aliased, the bitmap font follows the render mode
+        let mask = match text.get_font() {
+            None => {
+                let contours = get_text_contours(&BitmapFont{}, text.get_content(), position, size,
+                                                 text.get_alignment(), text.get_vertical_alignment(), TOLERANCE);
+                get_contours_mask(&self.image, &contours, FillRule::NonZero, self.render_mode, &self.transform)
+            },
+            Some(path) => {
+                let data = self.load_font(path)?;
+                let font = match TrueTypeFont::parse(&data) {
+                    None => {
+                        let new_error_string = format!("Cannot parse font {}", path);
+                        return Err(Box::new(GenericError::new(new_error_string)));
+                    },
+                    Some(font) => font
+                };
+                let contours = get_text_contours(&font, text.get_content(), position, size,
+                                                 text.get_alignment(), text.get_vertical_alignment(),
+                                                 get_flattening_tolerance(&self.transform));
+                get_contours_mask(&self.image, &contours, FillRule::NonZero, RenderMode::AntiAliased,
+                                  &self.transform)
+            }
+        };
+
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &mask, color);
+        return Ok(());
+    }
+
+    fn visit_polyline(&mut self, polyline: &Polyline) -> Result<(), Box<dyn Error>> {
+        let color = Rgba(color_to_rgba(&polyline.get_color()));
+        let points: Vec<FloatPoint> = polyline.get_points().iter().map(|point| FloatPoint::from(*point)).collect();
+        let mask = get_outline_mask(&self.image, &points, false, polyline.get_stroke(), self.render_mode,
+                                    &self.transform)?;
+
+        draw_mask(&mut self.image, self.clip.as_ref(), self.blend_mode, &mask, color);
+        return Ok(());
+    }
+
+    fn visit_blended_shape(&mut self, blended_shape: &BlendedShape) -> Result<(), Box<dyn Error>> {
+        let outer_blend_mode = std::mem::replace(&mut self.blend_mode, blended_shape.get_blend_mode());
+        let result = blended_shape.get_shape().accept(self);
+        self.blend_mode = outer_blend_mode;
+        return result;
+    }
+
+    // The shape is drawn into a layer of its own, below which its shadows and glows are drawn. The result is then
+    // drawn like a group.
+    fn visit_filtered_shape(&mut self, filtered_shape: &FilteredShape) -> Result<(), Box<dyn Error>> {
+        let layer = self.draw_layer_shapes(std::iter::once(filtered_shape.get_shape()))?;
+        let mut shape_layer = layer.clone();
+        let mut result = RgbaImage::new(layer.width(), layer.height());
+
+        for effect in filtered_shape.get_effects() {
+            match *effect {
+                Effect::DropShadow { offset, blur_radius, color } => {
+                    let shadow = blur_image(&get_silhouette(&layer, offset, &color), blur_radius);
+                    draw_layer(&mut result, None, BlendMode::Normal, &shadow, 100);
+                },
+                Effect::OuterGlow { blur_radius, color } => {
+                    let mut glow = blur_image(&get_silhouette(&layer, Point::new(0, 0), &color), blur_radius);
+                    for (x, y, pixel) in glow.enumerate_pixels_mut() {
+                        let shape_alpha = layer.get_pixel(x, y)[3] as u32;
+                        pixel[3] = ((pixel[3] as u32 * (255 - shape_alpha) + 127) / 255) as u8;
+                    }
+                    draw_layer(&mut result, None, BlendMode::Normal, &glow, 100);
+                },
+                Effect::Blur { radius } => {
+                    shape_layer = blur_image(&shape_layer, radius);
+                }
+            }
+        }
+
+        draw_layer(&mut result, None, BlendMode::Normal, &shape_layer, 100);
+        draw_layer(&mut self.image, self.clip.as_ref(), self.blend_mode, &result, 100);
+        return Ok(());
+    }
+
+    // The application leaves out the shapes of the layers that are not drawn
+    fn visit_layered_shape(&mut self, layered_shape: &LayeredShape) -> Result<(), Box<dyn Error>> {
+        return layered_shape.get_shape().accept(self);
+    }
+
+    fn visit_transformed_shape(&mut self, transformed_shape: &TransformedShape) -> Result<(), Box<dyn Error>> {
+        let outer_transform = self.transform;
+        let transform = transformed_shape.get_transform().then(&outer_transform);
+        if !transform.is_invertible() {
+            let error_string = format!("Transform {} scales a shape to nothing or to infinity", transform);
+            return Err(Box::new(GenericError::new(error_string)));
+        }
+        self.transform = transform;
+        let result = transformed_shape.get_shape().accept(self);
+        self.transform = outer_transform;
+        return result;
+    }
+
+    // Children are drawn into a layer of their own, so that overlapping children do not show through each other
+    // once the group opacity is applied
+    fn visit_group(&mut self, group: &Group) -> Result<(), Box<dyn Error>> {
+        let layer = self.draw_layer_shapes(group.get_shapes().iter().map(|shape| shape.as_ref()))?;
+        draw_layer(&mut self.image, self.clip.as_ref(), self.blend_mode, &layer, group.get_opacity());
+        return Ok(());
+    }
+
+    // Luminance uses the coefficients of SVG luminance masks on non-linear color channels
+    fn visit_soft_mask(&mut self, soft_mask: &SoftMask) -> Result<(), Box<dyn Error>> {
+        let layer = self.draw_layer_shapes(soft_mask.get_shapes().iter().map(|shape| shape.as_ref()))?;
+
+        let mut mask = Mask::new(layer.width(), layer.height());
+        for (x, y, pixel) in layer.enumerate_pixels() {
+            let alpha = pixel[3] as f32 / 255.0;
+            let coverage = match soft_mask.get_mode() {
+                MaskMode::Alpha => alpha,
+                MaskMode::Luminance => {
+                    let luminance = 0.2125 * pixel[0] as f32 + 0.7154 * pixel[1] as f32 + 0.0721 * pixel[2] as f32;
+                    alpha * luminance / 255.0
+                }
+            };
+            mask.add_coverage(x as i32, y as i32, coverage);
+        }
+
+        match &mut self.clip {
+            None => self.clip = Some(mask),
+            Some(current_clip) => current_clip.intersect(&mask)
+        };
+        return Ok(());
+    }
+
+    // The edges of a clipping rectangle lie half a pixel away from the centers of its border pixels, like the
+    // edges of a drawn rectangle
+    fn visit_clip(&mut self, clip: &Clip) -> Result<(), Box<dyn Error>> {
+        let (contour, fill_rule) = match clip.get_region() {
+            ClipRegion::Rectangle { top_left, height, width } => {
+                let left = top_left.x as f64 - 0.5;
+                let top = top_left.y as f64 - 0.5;
+                let right = left + *width as f64;
+                let bottom = top + *height as f64;
+                (Vec::from([FloatPoint::new(left, top), FloatPoint::new(right, top),
+                            FloatPoint::new(right, bottom), FloatPoint::new(left, bottom)]), FillRule::NonZero)
+            },
+            ClipRegion::Circle { center, radius } => {
+                let tolerance = get_flattening_tolerance(&self.transform);
+                (get_circle_points(FloatPoint::from(*center), *radius as f64, tolerance), FillRule::NonZero)
+            },
+            ClipRegion::Polygon(points) => {
+                (points.iter().map(|point| FloatPoint::from(*point)).collect(), FillRule::EvenOdd)
+            }
+        };
+
+        let clip_mask = get_contours_mask(&self.image, &[contour], fill_rule, self.render_mode, &self.transform);
+        match &mut self.clip {
+            None => self.clip = Some(clip_mask),
+            Some(current_clip) => current_clip.intersect(&clip_mask)
+        };
+        return Ok(());
     }
 }
 
+// Colors are parsed with an opacity between 0 and 100, while images store alpha between 0 and 255
 fn color_to_rgba(color: &Color) -> [u8; 4] {
-    return [color.r, color.g, color.b, color.a];
+    let opacity = std::cmp::min(color.a, 100) as u32;
+    let alpha = ((opacity * 255 + 50) / 100) as u8;
+    return [color.r, color.g, color.b, alpha];
 }
 
 fn is_pixel_inside_image(image: &RgbaImage, x: i32, y: i32) -> bool {
     return (x >= 0) && ((x as u32) < image.width()) && (y >= 0) && ((y as u32) < image.height());
 }
 
-fn put_pixel(image: &mut RgbaImage, x: i32, y: i32, color: Rgba<u8>) {
-    if is_pixel_inside_image(image, x, y) {
-        image.put_pixel(x as u32, y as u32, color);
+// Pixels outside of the clipping region are left untouched. Blend modes make partially covered pixels transparent
+// by the missing coverage of the shape and of the clipping region, while Porter-Duff operators are applied to them
+// with the color of the fully covered pixel and then mixed with the destination by that coverage.
+fn put_pixel(image: &mut RgbaImage, clip: Option<&Mask>, blend_mode: BlendMode, x: i32, y: i32, color: Rgba<u8>,
+             coverage: f32) {
+    if !is_pixel_inside_image(image, x, y) {
+        return;
+    }
+    let destination = *image.get_pixel(x as u32, y as u32);
+
+    if blend_mode.is_porter_duff() {
+        let coverage = coverage * clip.map_or(1.0, |clip| clip.get_coverage(x, y));
+        if coverage > 0.0 {
+            image.put_pixel(x as u32, y as u32, composite_porter_duff(destination, color, blend_mode, coverage));
+        }
+        return;
+    }
+
+    let mut color = color;
+    color[3] = (color[3] as f32 * coverage).round() as u8;
+    if let Some(clip) = clip {
+        color[3] = (color[3] as f32 * clip.get_coverage(x, y)).round() as u8;
+        if color[3] == 0 {
+            return;
+        }
+    }
+    image.put_pixel(x as u32, y as u32, blend(destination, color, blend_mode));
+}
+
+// Separable blend modes on non-premultiplied colors: the blended color replaces the source color where the
+// destination is opaque and is then composited source-over
+// https://www.w3.org/TR/compositing-1/#blending
+fn blend(destination: Rgba<u8>, source: Rgba<u8>, blend_mode: BlendMode) -> Rgba<u8> {
+    if blend_mode == BlendMode::Normal {
+        return blend_source_over(destination, source);
+    }
+
+    let source_alpha = source[3] as f32 / 255.0;
+    let destination_alpha = destination[3] as f32 / 255.0;
+    let out_alpha = source_alpha + destination_alpha * (1.0 - source_alpha);
+    if out_alpha <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+
+    let mut result = [0u8; 4];
+    for index in 0..3 {
+        let source_channel = source[index] as f32 / 255.0;
+        let destination_channel = destination[index] as f32 / 255.0;
+        let blended_channel = match blend_mode {
+            BlendMode::Multiply => destination_channel * source_channel,
+            BlendMode::Screen => destination_channel + source_channel - destination_channel * source_channel,
+            BlendMode::Overlay => {
+                if destination_channel <= 0.5 {
+                    2.0 * destination_channel * source_channel
+                } else {
+                    let doubled_channel = 2.0 * destination_channel - 1.0;
+                    doubled_channel + source_channel - doubled_channel * source_channel
+                }
+            },
+            BlendMode::Darken => f32::min(destination_channel, source_channel),
+            BlendMode::Lighten => f32::max(destination_channel, source_channel),
+            BlendMode::Difference => (destination_channel - source_channel).abs(),
+            _ => source_channel
+        };
+        let mixed_channel = (1.0 - destination_alpha) * source_channel + destination_alpha * blended_channel;
+        let out_channel = source_alpha * mixed_channel + destination_alpha * destination_channel * (1.0 - source_alpha);
+        result[index] = (out_channel / out_alpha * 255.0).round() as u8;
+    }
+    result[3] = (out_alpha * 255.0).round() as u8;
+    return Rgba(result);
+}
+
+// Porter-Duff operators on premultiplied colors, given by the fractions of the source and of the destination
+// that are kept. The result is mixed with the destination by the coverage.
+// https://www.w3.org/TR/compositing-1/#porterduffcompositingoperators
+fn composite_porter_duff(destination: Rgba<u8>, source: Rgba<u8>, blend_mode: BlendMode, coverage: f32) -> Rgba<u8> {
+    let source_alpha = source[3] as f32 / 255.0;
+    let destination_alpha = destination[3] as f32 / 255.0;
+    let (source_fraction, destination_fraction) = match blend_mode {
+        BlendMode::Clear => (0.0, 0.0),
+        BlendMode::Copy => (1.0, 0.0),
+        BlendMode::DestinationOver => (1.0 - destination_alpha, 1.0),
+        BlendMode::SourceIn => (destination_alpha, 0.0),
+        BlendMode::DestinationIn => (0.0, source_alpha),
+        BlendMode::SourceOut => (1.0 - destination_alpha, 0.0),
+        BlendMode::DestinationOut => (0.0, 1.0 - source_alpha),
+        BlendMode::SourceAtop => (destination_alpha, 1.0 - source_alpha),
+        BlendMode::DestinationAtop => (1.0 - destination_alpha, source_alpha),
+        BlendMode::Xor => (1.0 - destination_alpha, 1.0 - source_alpha),
+        _ => (1.0, 1.0 - source_alpha)
+    };
+
+    let composite_alpha = source_alpha * source_fraction + destination_alpha * destination_fraction;
+    let out_alpha = destination_alpha + (composite_alpha - destination_alpha) * coverage;
+    if out_alpha <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+
+    let mut result = [0u8; 4];
+    for index in 0..3 {
+        let source_channel = source[index] as f32 / 255.0 * source_alpha;
+        let destination_channel = destination[index] as f32 / 255.0 * destination_alpha;
+        let composite_channel = source_channel * source_fraction + destination_channel * destination_fraction;
+        let out_channel = destination_channel + (composite_channel - destination_channel) * coverage;
+        result[index] = (out_channel / out_alpha * 255.0).round().clamp(0.0, 255.0) as u8;
+    }
+    result[3] = (out_alpha * 255.0).round() as u8;
+    return Rgba(result);
+}
+
+// Porter-Duff source-over operator on non-premultiplied colors
+// https://en.wikipedia.org/wiki/Alpha_compositing
+fn blend_source_over(destination: Rgba<u8>, source: Rgba<u8>) -> Rgba<u8> {
+    let source_alpha = source[3] as f32 / 255.0;
+    let destination_alpha = destination[3] as f32 / 255.0;
+    let out_alpha = source_alpha + destination_alpha * (1.0 - source_alpha);
+
+    if out_alpha <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+
+    let mut result = [0u8; 4];
+    for index in 0..3 {
+        let source_channel = source[index] as f32 * source_alpha;
+        let destination_channel = destination[index] as f32 * destination_alpha * (1.0 - source_alpha);
+        result[index] = ((source_channel + destination_channel) / out_alpha).round() as u8;
+    }
+    result[3] = (out_alpha * 255.0).round() as u8;
+    return Rgba(result);
+}
+
+// Generalized Integer Bresenham's Algorithm for all quadrants, the points are generated one at a time
+// Stolen from https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+fn get_line_points(p0: Point, p1: Point) -> impl Iterator<Item = Point> {
+    let mut x0 = p0.x;
+    let mut y0 = p0.y;
+    let x1 = p1.x;
+    let y1 = p1.y;
+
+    let dx = (x1 - x0).abs();
+    let sx = if x0 < x1 {1} else {-1};
+    let dy = -(y1 - y0).abs();
+    let sy = if y0 < y1 {1} else {-1};
+    let mut err = dx + dy;
+    let mut is_done = false;
+
+    return std::iter::from_fn(move || {
+        if is_done {
+            return None;
+        }
+        let point = Point::new(x0, y0);
+        if (x0 == x1) && (y0 == y1) {
+            is_done = true;
+            return Some(point);
+        }
+
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y0 += sy;
+        }
+        return Some(point);
+    });
+}
+
+// Circle generation using Brasenham's algorithm
+// https://www.geeksforgeeks.org/bresenhams-circle-drawing-algorithm/
+fn get_circle_outline_points(center: Point, radius: u32) -> Vec<Point> {
+    let mut points: Vec<Point> = Vec::new();
+    let mut x: i32 = 0;
+    let mut y: i32 = radius as i32;
+    let mut d: i32 = 3 - 2 * (radius as i32);
+
+    while y >= x {
+        add_points_in_8_parts_around_circle(&mut points, center.x, center.y, x, y);
+        x += 1;
+        if d > 0 {
+            y -= 1;
+            d += 4 * (x - y) + 10;
+        } else {
+            d += 4 * x + 6;
+        }
     }
+    return points;
 }
 
-fn put_pixels_in_8_parts_around_circle(image: &mut RgbaImage, x_circle: i32, y_cirlce: i32,
-                                       x: i32, y: i32, color: Rgba<u8>) {
+fn add_points_in_8_parts_around_circle(points: &mut Vec<Point>, x_circle: i32, y_cirlce: i32, x: i32, y: i32) {
     let add_to_x = [  x, -x,  x, -x,  y, -y,  y, -y ];
     let add_to_y = [  y,  y, -y, -y,  x,  x, -x, -x ];
     for index in 0..add_to_x.len() {
-        put_pixel(image, x_circle + add_to_x[index], y_cirlce + add_to_y[index], color);
+        points.push(Point::new(x_circle + add_to_x[index], y_cirlce + add_to_y[index]));
     }
 }
 
-fn draw_polygon_outline(points: &Vec<Point>, outline_color: Color, draw_visitor: &mut DrawShapeVisitor)
--> Result<(), Box<dyn Error>> {
+// The circle is convex, so every row of its interior lies between the leftmost and the rightmost
+// outline pixels of that row
+fn get_circle_spans(outline: &[Point], center: Point, radius: u32) -> Vec<Span> {
+    let y_top = center.y - radius as i32;
+    let mut row_extremes = vec![(i32::MAX, i32::MIN); 2 * radius as usize + 1];
 
-    for point_index in 0..points.len() {
-        let p0 = points[point_index];
-        let p1 = points[(point_index + 1) % points.len()];
-        draw_visitor.visit_line(&Line::new(p0, p1, outline_color))?;
+    for point in outline {
+        let row = &mut row_extremes[(point.y - y_top) as usize];
+        row.0 = std::cmp::min(row.0, point.x);
+        row.1 = std::cmp::max(row.1, point.x);
     }
-    return Ok(());
-}
 
-fn get_polygon_center(points: &Vec<Point>) -> Point {
-    let mut center = Point::new(-1, -1);
+    let mut spans: Vec<Span> = Vec::new();
+    for (row_index, (x_left, x_right)) in row_extremes.into_iter().enumerate() {
+        if x_left < x_right {
+            spans.push(Span::new(y_top + row_index as i32, x_left, x_right));
+        }
+    }
+    return spans;
+}
 
-    for point in points {
-        center.x += point.x;
-        center.y += point.y;
+// Mask of the segments between consecutive points, closed outlines also join the last point
+// with the first one. Dashed outlines are split into open polylines, one for every dash.
+fn get_outline_mask(image: &RgbaImage, points: &[FloatPoint], is_closed: bool, stroke: &Stroke,
+                    render_mode: RenderMode, transform: &Transform) -> Result<Mask, Box<dyn Error>> {
+    if !stroke.is_dashed() {
+        let polylines = [points.to_vec()];
+        return Ok(get_polylines_mask(image, &polylines, is_closed, false, stroke, render_mode, transform));
     }
-    center.x /= points.len() as i32;
-    center.y /= points.len() as i32;
 
-    return center;
+    let dashes = match get_dashes(points, is_closed, stroke.get_dash_array(), stroke.get_dash_offset()) {
+        Some(dashes) => dashes,
+        None => return Err(Box::new(GenericError::new(format!("Dashed outline has more than {} dashes",
+                                                              MAX_DASHES))))
+    };
+    return Ok(get_polylines_mask(image, &dashes, false, true, stroke, render_mode, transform));
 }
 
-fn flood_fill(image: &mut RgbaImage, x_start: i32, y_start: i32,
-              outline_color: Rgba<u8>, fill_color: Rgba<u8>) -> Result<(), Box<dyn Error>> {
-
-    if !is_pixel_inside_image(image, x_start, y_start) {
-        let error_name = format!("Coordinates {} are outside canvas", Point::new(x_start, y_start));
-        return Err(Box::new(GenericError::new(error_name)));
+// Thin strokes are drawn as lines, thick ones are filled as polygons. The outline of thick strokes is
+// computed before transforming it so that their width is transformed too, thin strokes stay one pixel wide.
+// Thin dashes leave out their end pixel, so that a dash and the gap after it are as long as the pattern
+// says, unless the whole dash is a single pixel.
+fn get_polylines_mask(image: &RgbaImage, polylines: &[Vec<FloatPoint>], is_closed: bool, are_dashes: bool,
+                      stroke: &Stroke, render_mode: RenderMode, transform: &Transform) -> Mask {
+    if stroke.get_width() > 1 {
+        let mut contours: Vec<Vec<FloatPoint>> = Vec::new();
+        for points in polylines {
+            contours.extend(get_polyline_stroke_contours(points, is_closed, stroke,
+                                                         get_flattening_tolerance(transform)));
+        }
+        return get_contours_mask(image, &contours, FillRule::NonZero, render_mode, transform);
     }
 
-    let mut queue: VecDeque<Point> = VecDeque::new();
-    queue.push_back(Point::new(x_start, y_start));
+    let mut mask = Mask::new(image.width(), image.height());
+    for points in polylines {
+        let points: Vec<FloatPoint> = points.iter().map(|point| transform.apply(*point)).collect();
+        let segments_number = if is_closed { points.len() } else { points.len().saturating_sub(1) };
+
+        let is_dot = points.iter().all(|point| point.round() == points[0].round());
+
+        if segments_number == 0 && !points.is_empty() {
+            mask.add_coverage(points[0].round().x, points[0].round().y, 1.0);
+        }
+        for point_index in 0..segments_number {
+            let (p0, p1) = match clip_segment(points[point_index], points[(point_index + 1) % points.len()],
+                                              mask.get_width(), mask.get_height()) {
+                None => continue,
+                Some((p0, p1)) => (p0.round(), p1.round())
+            };
+            let include_end = !are_dashes || is_dot || point_index + 1 < segments_number;
 
-    let mut visited = vec![vec![false; image.height() as usize]; image.width() as usize];
+            match render_mode {
+                RenderMode::Aliased => {
+                    for point in get_line_points(p0, p1) {
+                        if include_end || point != p1 {
+                            mask.add_coverage(point.x, point.y, 1.0);
+                        }
+                    }
+                },
+                RenderMode::AntiAliased => add_line_coverage(&mut mask, p0, p1, include_end)
+            };
+        }
+    }
+    return mask;
+}
 
-    while !queue.is_empty() {
-        let point = queue.pop_front().unwrap();
+// Liang-Barsky algorithm, clips the segment to the image and a margin as large as the image around it.
+// Segments ending in the margin are not changed, so they keep the same pixels as without clipping, and
+// longer ones are drawn in time proportional to the size of the image.
+// https://en.wikipedia.org/wiki/Liang%E2%80%93Barsky_algorithm
+fn clip_segment(p0: FloatPoint, p1: FloatPoint, width: u32, height: u32) -> Option<(FloatPoint, FloatPoint)> {
+    let (width, height) = (width as f64, height as f64);
+    let delta = p1.subtract(p0);
+    let (mut t_start, mut t_end) = (0.0f64, 1.0f64);
+    let boundaries = [(-delta.x, p0.x + width), (delta.x, 2.0 * width - p0.x),
+                      (-delta.y, p0.y + height), (delta.y, 2.0 * height - p0.y)];
 
-        if visited[point.x as usize][point.y as usize] {
+    for (p, q) in boundaries {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
             continue;
         }
-        visited[point.x as usize][point.y as usize] = true;
+        let t = q / p;
+        if p < 0.0 {
+            t_start = t_start.max(t);
+        } else {
+            t_end = t_end.min(t);
+        }
+    }
+    if t_start > t_end {
+        return None;
+    }
 
-        put_pixel(image, point.x, point.y, fill_color);
+    let start = if t_start > 0.0 { p0.add(delta.scale(t_start)) } else { p0 };
+    let end = if t_end < 1.0 { p0.add(delta.scale(t_end)) } else { p1 };
+    return Some((start, end));
+}
+
+// Curves flattened before being transformed stay within the tolerance once transformed
+fn get_flattening_tolerance(transform: &Transform) -> f64 {
+    return TOLERANCE / transform.get_scale_factor();
+}
+
+fn get_contours_mask(image: &RgbaImage, contours: &[Vec<FloatPoint>], fill_rule: FillRule,
+                     render_mode: RenderMode, transform: &Transform) -> Mask {
+    let mut mask = Mask::new(image.width(), image.height());
 
-        let neighbors = [ Point::new(point.x + 1, point.y),
-                          Point::new(point.x - 1, point.y),
-                          Point::new(point.x, point.y + 1),
-                          Point::new(point.x, point.y - 1) ];
+    let transformed_contours: Vec<Vec<FloatPoint>>;
+    let contours = if transform.is_identity() {
+        contours
+    } else {
+        transformed_contours = contours.iter()
+            .map(|contour| contour.iter().map(|point| transform.apply(*point)).collect())
+            .collect();
+        &transformed_contours
+    };
 
-        for neighbor in neighbors {
-            if is_pixel_inside_image(image, neighbor.x, neighbor.y)
-               && !is_the_same_color(*image.get_pixel(neighbor.x as u32, neighbor.y as u32), outline_color) {
-                queue.push_back(neighbor);
+    match render_mode {
+        RenderMode::Aliased => {
+            for span in get_polygon_spans(contours, fill_rule, image.width(), image.height()) {
+                mask.add_span(&span);
+            }
+        },
+        RenderMode::AntiAliased => add_polygon_coverage(contours, fill_rule, &mut mask)
+    };
+    return mask;
+}
+
+// Color of a paint at a pixel center, gradients are interpolated channel by channel between their stops.
+// The image of a texture paint is loaded by the caller.
+fn get_paint_color(paint: &Paint, texture: Option<&RgbaImage>, point: FloatPoint) -> Rgba<u8> {
+    let (stops, position) = match paint {
+        Paint::Solid(color) => return Rgba(color_to_rgba(color)),
+        Paint::Texture { origin, .. } => {
+            return match texture {
+                Some(texture) if texture.width() > 0 && texture.height() > 0 => {
+                    let x = (point.x - origin.x).floor().rem_euclid(texture.width() as f64) as u32;
+                    let y = (point.y - origin.y).floor().rem_euclid(texture.height() as f64) as u32;
+                    *texture.get_pixel(x, y)
+                },
+                _ => Rgba([0, 0, 0, 0])
+            };
+        },
+        Paint::Pattern { pattern, angle, spacing, line_width, foreground, background } => {
+            let coverage = get_pattern_coverage(*pattern, angle.to_radians(), *spacing, *line_width, point);
+            let mut source = Rgba(color_to_rgba(foreground));
+            source[3] = (source[3] as f64 * coverage).round() as u8;
+            return blend_source_over(Rgba(color_to_rgba(background)), source);
+        },
+        Paint::LinearGradient { start, end, stops } => {
+            let direction = end.subtract(*start);
+            let offset = point.subtract(*start);
+            let length_squared = direction.x * direction.x + direction.y * direction.y;
+            if length_squared == 0.0 {
+                (stops, 1.0)
+            } else {
+                (stops, (offset.x * direction.x + offset.y * direction.y) / length_squared)
+            }
+        },
+        Paint::RadialGradient { center, radius, stops } => {
+            if *radius == 0.0 {
+                (stops, 1.0)
+            } else {
+                (stops, point.subtract(*center).length() / radius)
             }
         }
+    };
+
+    let first = &stops[0];
+    let last = &stops[stops.len() - 1];
+    if position <= first.offset {
+        return Rgba(color_to_rgba(&first.color));
+    }
+    if position >= last.offset {
+        return Rgba(color_to_rgba(&last.color));
     }
-    return Ok(());
+
+    let next_index = stops.iter().position(|stop| stop.offset > position).unwrap_or(stops.len() - 1);
+    let previous = &stops[next_index - 1];
+    let next = &stops[next_index];
+    let ratio = (position - previous.offset) / (next.offset - previous.offset);
+
+    let previous_rgba = color_to_rgba(&previous.color);
+    let next_rgba = color_to_rgba(&next.color);
+    let mut rgba = [0u8; 4];
+    for channel in 0..4 {
+        rgba[channel] = (previous_rgba[channel] as f64
+                         + (next_rgba[channel] as f64 - previous_rgba[channel] as f64) * ratio).round() as u8;
+    }
+    return Rgba(rgba);
 }
 
-fn is_the_same_color(color1: Rgba<u8>, color2: Rgba<u8>) -> bool {
-    for index in 0..3 {
-        if color1[index] != color2[index] {
-            return false;
+// Part of the pixel centered on the point covered by the foreground of the pattern, the lines and dots are
+// given a one pixel wide smooth edge
+fn get_pattern_coverage(pattern: Pattern, angle: f64, spacing: f64, line_width: f64, point: FloatPoint) -> f64 {
+    // Coordinates along the lines (u) and across them (v)
+    let u = point.x * angle.cos() + point.y * angle.sin();
+    let v = -point.x * angle.sin() + point.y * angle.cos();
+    let u_distance = (u - spacing * (u / spacing).round()).abs();
+    let v_distance = (v - spacing * (v / spacing).round()).abs();
+
+    let distance = match pattern {
+        Pattern::Hatch => v_distance,
+        Pattern::CrossHatch => f64::min(u_distance, v_distance),
+        Pattern::Dots => u_distance.hypot(v_distance)
+    };
+    return (line_width / 2.0 + 0.5 - distance).clamp(0.0, 1.0);
+}
+
+// Composites a layer of the same size as the image over it, made transparent by the opacity between 0 and 100
+fn draw_layer(image: &mut RgbaImage, clip: Option<&Mask>, blend_mode: BlendMode, layer: &RgbaImage, opacity: u8) {
+    let opacity = std::cmp::min(opacity, 100) as u32;
+    for (x, y, pixel) in layer.enumerate_pixels() {
+        let mut source = *pixel;
+        source[3] = ((source[3] as u32 * opacity + 50) / 100) as u8;
+        if source[3] > 0 {
+            put_pixel(image, clip, blend_mode, x as i32, y as i32, source, 1.0);
+        }
+    }
+}
+
+// The shape of the layer filled with the color, moved by the offset
+fn get_silhouette(layer: &RgbaImage, offset: Point, color: &Color) -> RgbaImage {
+    let [r, g, b, a] = color_to_rgba(color);
+    let mut silhouette = RgbaImage::new(layer.width(), layer.height());
+    for (x, y, pixel) in layer.enumerate_pixels() {
+        // Saturated coordinates of offsets beyond the range of numbers are outside the image
+        let (x, y) = ((x as i32).saturating_add(offset.x), (y as i32).saturating_add(offset.y));
+        if pixel[3] > 0 && is_pixel_inside_image(&silhouette, x, y) {
+            let alpha = ((pixel[3] as u32 * a as u32 + 127) / 255) as u8;
+            silhouette.put_pixel(x as u32, y as u32, Rgba([r, g, b, alpha]));
+        }
+    }
+    return silhouette;
+}
+
+fn draw_mask(image: &mut RgbaImage, clip: Option<&Mask>, blend_mode: BlendMode, mask: &Mask, color: Rgba<u8>) {
+    draw_mask_with(image, clip, blend_mode, mask, |_, _| color);
+}
+
+// The color of every covered pixel is given by its coordinates and made transparent by the missing coverage
+fn draw_mask_with<F>(image: &mut RgbaImage, clip: Option<&Mask>, blend_mode: BlendMode, mask: &Mask, get_color: F)
+where F: Fn(i32, i32) -> Rgba<u8> {
+    let (x_min, y_min, x_max, y_max) = match mask.get_bounds() {
+        None => return,
+        Some(bounds) => bounds
+    };
+
+    for y in y_min..=y_max {
+        for x in x_min..=x_max {
+            let coverage = mask.get_coverage(x, y);
+            if coverage > 0.0 {
+                put_pixel(image, clip, blend_mode, x, y, get_color(x, y), coverage);
+            }
         }
     }
-    return true;
 }
@@ -8,7 +8,23 @@ use crate::shapes::{canvas::Canvas,
                     rectangle::Rectangle,
                     diamond::Diamond,
                     polygon::Polygon,
-                    circle::Circle};
+                    circle::Circle,
+                    ellipse::Ellipse,
+                    arc::Arc,
+                    pie::Pie,
+                    path::Path,
+                    regular_polygon::RegularPolygon,
+                    star::Star,
+                    polyline::Polyline,
+                    image::Image,
+                    text::Text,
+                    transformed_shape::TransformedShape,
+                    group::Group,
+                    clip::Clip,
+                    soft_mask::SoftMask,
+                    blended_shape::BlendedShape,
+                    filtered_shape::FilteredShape,
+                    layered_shape::LayeredShape};
 
 use std::error::Error;
 
@@ -63,4 +79,92 @@ impl ShapeVisitor for PrintShapeVisitor {
         println!("{}", circle);
         return Ok(());
     }
+
+    fn visit_ellipse(&mut self, ellipse: &Ellipse) -> Result<(), Box<dyn Error>> {
+        println!("{}", ellipse);
+        return Ok(());
+    }
+
+    fn visit_arc(&mut self, arc: &Arc) -> Result<(), Box<dyn Error>> {
+        println!("{}", arc);
+        return Ok(());
+    }
+
+    fn visit_pie(&mut self, pie: &Pie) -> Result<(), Box<dyn Error>> {
+        println!("{}", pie);
+        return Ok(());
+    }
+
+    fn visit_path(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        println!("{}", path);
+        return Ok(());
+    }
+
+    fn visit_regular_polygon(&mut self, regular_polygon: &RegularPolygon) -> Result<(), Box<dyn Error>> {
+        println!("{}", regular_polygon);
+        return Ok(());
+    }
+
+    fn visit_star(&mut self, star: &Star) -> Result<(), Box<dyn Error>> {
+        println!("{}", star);
+        return Ok(());
+    }
+
+    fn visit_polyline(&mut self, polyline: &Polyline) -> Result<(), Box<dyn Error>> {
+        println!("{}", polyline);
+        return Ok(());
+    }
+
+    fn visit_image(&mut self, image: &Image) -> Result<(), Box<dyn Error>> {
+        println!("{}", image);
+        return Ok(());
+    }
+
+    fn visit_text(&mut self, text: &Text) -> Result<(), Box<dyn Error>> {
+        println!("{}", text);
+        return Ok(());
+    }
+
+    fn visit_transformed_shape(&mut self, transformed_shape: &TransformedShape) -> Result<(), Box<dyn Error>> {
+        println!("{}", transformed_shape);
+        return transformed_shape.get_shape().accept(self);
+    }
+
+    fn visit_group(&mut self, group: &Group) -> Result<(), Box<dyn Error>> {
+        println!("{}", group);
+        for shape in group.get_shapes() {
+            shape.accept(self)?;
+        }
+        println!("End group");
+        return Ok(());
+    }
+
+    fn visit_clip(&mut self, clip: &Clip) -> Result<(), Box<dyn Error>> {
+        println!("{}", clip);
+        return Ok(());
+    }
+
+    fn visit_soft_mask(&mut self, soft_mask: &SoftMask) -> Result<(), Box<dyn Error>> {
+        println!("{}", soft_mask);
+        for shape in soft_mask.get_shapes() {
+            shape.accept(self)?;
+        }
+        println!("End soft mask");
+        return Ok(());
+    }
+
+    fn visit_blended_shape(&mut self, blended_shape: &BlendedShape) -> Result<(), Box<dyn Error>> {
+        println!("{}", blended_shape);
+        return blended_shape.get_shape().accept(self);
+    }
+
+    fn visit_filtered_shape(&mut self, filtered_shape: &FilteredShape) -> Result<(), Box<dyn Error>> {
+        println!("{}", filtered_shape);
+        return filtered_shape.get_shape().accept(self);
+    }
+
+    fn visit_layered_shape(&mut self, layered_shape: &LayeredShape) -> Result<(), Box<dyn Error>> {
+        println!("{}", layered_shape);
+        return layered_shape.get_shape().accept(self);
+    }
 }
@@ -6,7 +6,23 @@ use crate::shapes::{canvas::Canvas,
                     rectangle::Rectangle,
                     diamond::Diamond,
                     polygon::Polygon,
-                    circle::Circle};
+                    circle::Circle,
+                    ellipse::Ellipse,
+                    arc::Arc,
+                    pie::Pie,
+                    path::Path,
+                    regular_polygon::RegularPolygon,
+                    star::Star,
+                    polyline::Polyline,
+                    image::Image,
+                    text::Text,
+                    transformed_shape::TransformedShape,
+                    group::Group,
+                    clip::Clip,
+                    soft_mask::SoftMask,
+                    blended_shape::BlendedShape,
+                    filtered_shape::FilteredShape,
+                    layered_shape::LayeredShape};
 
 use std::error::Error;
 
@@ -19,4 +35,20 @@ pub trait ShapeVisitor {
     fn visit_diamond(&mut self, diamond: &Diamond) -> Result<(), Box<dyn Error>>;
     fn visit_polygon(&mut self, polygon: &Polygon) -> Result<(), Box<dyn Error>>;
     fn visit_circle(&mut self, circle: &Circle) -> Result<(), Box<dyn Error>>;
+    fn visit_ellipse(&mut self, ellipse: &Ellipse) -> Result<(), Box<dyn Error>>;
+    fn visit_arc(&mut self, arc: &Arc) -> Result<(), Box<dyn Error>>;
+    fn visit_pie(&mut self, pie: &Pie) -> Result<(), Box<dyn Error>>;
+    fn visit_path(&mut self, path: &Path) -> Result<(), Box<dyn Error>>;
+    fn visit_regular_polygon(&mut self, regular_polygon: &RegularPolygon) -> Result<(), Box<dyn Error>>;
+    fn visit_star(&mut self, star: &Star) -> Result<(), Box<dyn Error>>;
+    fn visit_polyline(&mut self, polyline: &Polyline) -> Result<(), Box<dyn Error>>;
+    fn visit_image(&mut self, image: &Image) -> Result<(), Box<dyn Error>>;
+    fn visit_text(&mut self, text: &Text) -> Result<(), Box<dyn Error>>;
+    fn visit_transformed_shape(&mut self, transformed_shape: &TransformedShape) -> Result<(), Box<dyn Error>>;
+    fn visit_group(&mut self, group: &Group) -> Result<(), Box<dyn Error>>;
+    fn visit_clip(&mut self, clip: &Clip) -> Result<(), Box<dyn Error>>;
+    fn visit_soft_mask(&mut self, soft_mask: &SoftMask) -> Result<(), Box<dyn Error>>;
+    fn visit_blended_shape(&mut self, blended_shape: &BlendedShape) -> Result<(), Box<dyn Error>>;
+    fn visit_filtered_shape(&mut self, filtered_shape: &FilteredShape) -> Result<(), Box<dyn Error>>;
+    fn visit_layered_shape(&mut self, layered_shape: &LayeredShape) -> Result<(), Box<dyn Error>>;
 }
@@ -8,3 +8,19 @@ pub mod rectangle;
 pub mod diamond;
 pub mod polygon;
 pub mod circle;
+pub mod ellipse;
+pub mod arc;
+pub mod pie;
+pub mod path;
+pub mod regular_polygon;
+pub mod star;
+pub mod polyline;
+pub mod image;
+pub mod text;
+pub mod transformed_shape;
+pub mod group;
+pub mod clip;
+pub mod soft_mask;
+pub mod blended_shape;
+pub mod filtered_shape;
+pub mod layered_shape;
@@ -0,0 +1,76 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{point::Point, color::Color, stroke::Stroke};
+
+use std::error::Error;
+
+// Open part of an ellipse's outline, going clockwise from the start angle to the end angle
+pub struct Arc {
+    center: Point,
+    horizontal_radius: u32,
+    vertical_radius: u32,
+    // Clockwise, in degrees
+    start_angle: f64,
+    end_angle: f64,
+    rotation: f64,
+    color: Color,
+    stroke: Stroke
+}
+
+impl Arc {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(center: Point, horizontal_radius: u32, vertical_radius: u32,
+               start_angle: f64, end_angle: f64, rotation: f64, color: Color, stroke: Stroke)
+    -> Self {
+        return Arc{center: center, horizontal_radius: horizontal_radius, vertical_radius: vertical_radius,
+                   start_angle: start_angle, end_angle: end_angle, rotation: rotation,
+                   color: color, stroke: stroke};
+    }
+
+    pub fn get_center(&self) -> Point {
+        return self.center;
+    }
+
+    pub fn get_horizontal_radius(&self) -> u32 {
+        return self.horizontal_radius;
+    }
+
+    pub fn get_vertical_radius(&self) -> u32 {
+        return self.vertical_radius;
+    }
+
+    pub fn get_start_angle(&self) -> f64 {
+        return self.start_angle;
+    }
+
+    pub fn get_end_angle(&self) -> f64 {
+        return self.end_angle;
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        return self.rotation;
+    }
+
+    pub fn get_color(&self) -> Color {
+        return self.color;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
+}
+
+impl Shape for Arc {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_arc(self);
+    }
+}
+
+impl std::fmt::Display for Arc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Arc center: {} horizontal radius: {} vertical radius: {} start angle: {} end angle: {} rotation: {} color: {} stroke {}",
+                      self.center, self.horizontal_radius, self.vertical_radius,
+                      self.start_angle, self.end_angle, self.rotation, self.color, self.stroke);
+    }
+}
@@ -0,0 +1,38 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::blend_mode::BlendMode;
+
+use std::error::Error;
+
+// Shape whose colors are combined with the ones below it by a blend mode other than the normal one
+pub struct BlendedShape {
+    shape: Box<dyn Shape>,
+    blend_mode: BlendMode
+}
+
+impl BlendedShape {
+    pub fn new(shape: Box<dyn Shape>, blend_mode: BlendMode) -> Self {
+        return BlendedShape{shape: shape, blend_mode: blend_mode};
+    }
+
+    pub fn get_shape(&self) -> &dyn Shape {
+        return self.shape.as_ref();
+    }
+
+    pub fn get_blend_mode(&self) -> BlendMode {
+        return self.blend_mode;
+    }
+}
+
+impl Shape for BlendedShape {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_blended_shape(self);
+    }
+}
+
+impl std::fmt::Display for BlendedShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Blended shape blend mode: {}", self.blend_mode);
+    }
+}
@@ -31,7 +31,7 @@ impl Canvas {
 
 impl Shape for Canvas {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_canvas(&self);
+        return shape_visitor.visit_canvas(self);
     }
 }
 
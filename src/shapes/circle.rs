@@ -1,7 +1,7 @@
 
 use crate::shapes::shape::Shape;
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
-use crate::utils::{point::Point, color::Color};
+use crate::utils::{point::Point, color::Color, paint::Paint, stroke::Stroke};
 
 use std::error::Error;
 
@@ -9,17 +9,19 @@ pub struct Circle {
     center: Point,
     radius: u32,
     outline_color: Color,
-    fill_color: Color
+    fill_paint: Paint,
+    stroke: Stroke
 }
 
 impl Circle {
     pub fn new(center: Point, radius: u32,
            outline_color: Color,
-           fill_color: Color)
+           fill_paint: Paint,
+           stroke: Stroke)
     -> Self {
 
         return Circle{center: center, radius: radius,
-                      outline_color: outline_color, fill_color: fill_color};
+                      outline_color: outline_color, fill_paint: fill_paint, stroke: stroke};
     }
 
     pub fn get_center(&self) -> Point {
@@ -34,20 +36,24 @@ impl Circle {
         return self.outline_color;
     }
 
-    pub fn get_fill_color(&self) -> Color {
-        return self.fill_color;
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
     }
 }
 
 impl Shape for Circle {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_circle(&self);
+        return shape_visitor.visit_circle(self);
     }
 }
 
 impl std::fmt::Display for Circle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(f, "Circle center: {} radius: {} outline color: {} fill color: {}",
-                      self.center, self.radius, self.outline_color, self.fill_color);
+        return write!(f, "Circle center: {} radius: {} outline color: {} fill paint: {} stroke {}",
+                      self.center, self.radius, self.outline_color, self.fill_paint, self.stroke);
     }
 }
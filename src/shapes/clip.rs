@@ -0,0 +1,57 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::point::Point;
+use std::vec::Vec;
+use std::string::String;
+
+use std::error::Error;
+
+pub enum ClipRegion {
+    Rectangle { top_left: Point, height: u32, width: u32 },
+    Circle { center: Point, radius: u32 },
+    Polygon(Vec<Point>)
+}
+
+// Restricts drawing to a region, for the shapes that follow it up to the end of the enclosing group or file.
+// Pixels outside of the region are left untouched and several regions restrict drawing to their intersection.
+pub struct Clip {
+    region: ClipRegion
+}
+
+impl Clip {
+    pub fn new(region: ClipRegion) -> Self {
+        return Clip{region: region};
+    }
+
+    pub fn get_region(&self) -> &ClipRegion {
+        return &self.region;
+    }
+}
+
+impl Shape for Clip {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_clip(self);
+    }
+}
+
+impl std::fmt::Display for ClipRegion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ClipRegion::Rectangle { top_left, height, width } => {
+                write!(f, "rectangle top left: {} height: {} width: {}", top_left, height, width)
+            },
+            ClipRegion::Circle { center, radius } => write!(f, "circle center: {} radius: {}", center, radius),
+            ClipRegion::Polygon(points) => {
+                let points_strings: Vec<String> = points.iter().map(|point| point.to_string()).collect();
+                write!(f, "polygon points: {}", points_strings.join(", "))
+            }
+        };
+    }
+}
+
+impl std::fmt::Display for Clip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Clip region: {}", self.region);
+    }
+}
@@ -1,7 +1,7 @@
 
 use crate::shapes::shape::Shape;
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
-use crate::utils::{point::Point, color::Color};
+use crate::utils::{point::Point, color::Color, paint::Paint, stroke::Stroke};
 
 use std::error::Error;
 
@@ -10,18 +10,20 @@ pub struct Diamond {
     horizontal_diagonal: u32,
     vertical_diagonal: u32,
     outline_color: Color,
-    fill_color: Color
+    fill_paint: Paint,
+    stroke: Stroke
 }
 
 impl Diamond {
     pub fn new(center: Point, horizontal_diagonal: u32, vertical_diagonal: u32,
-           outline_color: Color, fill_color: Color)
+           outline_color: Color, fill_paint: Paint, stroke: Stroke)
     -> Self {
         return Diamond{center: center,
                        horizontal_diagonal: horizontal_diagonal,
                        vertical_diagonal: vertical_diagonal, 
                        outline_color: outline_color,
-                       fill_color: fill_color};
+                       fill_paint: fill_paint,
+                       stroke: stroke};
     }
 
     pub fn get_center(&self) -> Point {
@@ -40,21 +42,25 @@ impl Diamond {
         return self.outline_color;
     }
 
-    pub fn get_fill_color(&self) -> Color {
-        return self.fill_color;
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
     }
 }
 
 impl Shape for Diamond {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_diamond(&self);
+        return shape_visitor.visit_diamond(self);
     }
 }
 
 impl std::fmt::Display for Diamond {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(f, "Diamond center: {} horizontal diagonal: {} vertical diagonal: {} outlone color: {} fill color: {}",
+        return write!(f, "Diamond center: {} horizontal diagonal: {} vertical diagonal: {} outlone color: {} fill paint: {} stroke {}",
                       self.center, self.horizontal_diagonal, self.vertical_diagonal,
-                      self.outline_color, self.fill_color);
+                      self.outline_color, self.fill_paint, self.stroke);
     }
 }
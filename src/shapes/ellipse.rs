@@ -0,0 +1,69 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{point::Point, color::Color, paint::Paint, stroke::Stroke};
+
+use std::error::Error;
+
+pub struct Ellipse {
+    center: Point,
+    horizontal_radius: u32,
+    vertical_radius: u32,
+    // Clockwise, in degrees
+    rotation: f64,
+    outline_color: Color,
+    fill_paint: Paint,
+    stroke: Stroke
+}
+
+impl Ellipse {
+    pub fn new(center: Point, horizontal_radius: u32, vertical_radius: u32, rotation: f64,
+               outline_color: Color, fill_paint: Paint, stroke: Stroke)
+    -> Self {
+        return Ellipse{center: center, horizontal_radius: horizontal_radius, vertical_radius: vertical_radius,
+                       rotation: rotation, outline_color: outline_color, fill_paint: fill_paint,
+                       stroke: stroke};
+    }
+
+    pub fn get_center(&self) -> Point {
+        return self.center;
+    }
+
+    pub fn get_horizontal_radius(&self) -> u32 {
+        return self.horizontal_radius;
+    }
+
+    pub fn get_vertical_radius(&self) -> u32 {
+        return self.vertical_radius;
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        return self.rotation;
+    }
+
+    pub fn get_outline_color(&self) -> Color {
+        return self.outline_color;
+    }
+
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
+}
+
+impl Shape for Ellipse {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_ellipse(self);
+    }
+}
+
+impl std::fmt::Display for Ellipse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Ellipse center: {} horizontal radius: {} vertical radius: {} rotation: {} outline color: {} fill paint: {} stroke {}",
+                      self.center, self.horizontal_radius, self.vertical_radius, self.rotation,
+                      self.outline_color, self.fill_paint, self.stroke);
+    }
+}
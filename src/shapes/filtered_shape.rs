@@ -0,0 +1,43 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::effect::Effect;
+use std::vec::Vec;
+
+use std::error::Error;
+
+// Shape drawn offscreen and then filtered by its effects, in the order they are written
+pub struct FilteredShape {
+    shape: Box<dyn Shape>,
+    effects: Vec<Effect>
+}
+
+impl FilteredShape {
+    pub fn new(shape: Box<dyn Shape>, effects: Vec<Effect>) -> Self {
+        return FilteredShape{shape: shape, effects: effects};
+    }
+
+    pub fn get_shape(&self) -> &dyn Shape {
+        return self.shape.as_ref();
+    }
+
+    pub fn get_effects(&self) -> &Vec<Effect> {
+        return &self.effects;
+    }
+}
+
+impl Shape for FilteredShape {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_filtered_shape(self);
+    }
+}
+
+impl std::fmt::Display for FilteredShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Filtered shape effects:")?;
+        for effect in &self.effects {
+            write!(f, " [{}]", effect)?;
+        }
+        return Ok(());
+    }
+}
@@ -0,0 +1,39 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use std::vec::Vec;
+
+use std::error::Error;
+
+// Shapes drawn together and then composited as a single image
+pub struct Group {
+    shapes: Vec<Box<dyn Shape>>,
+    // Between 0 and 100 like the opacity of colors, applied to the group as a whole
+    opacity: u8
+}
+
+impl Group {
+    pub fn new(shapes: Vec<Box<dyn Shape>>, opacity: u8) -> Self {
+        return Group{shapes: shapes, opacity: opacity};
+    }
+
+    pub fn get_shapes(&self) -> &Vec<Box<dyn Shape>> {
+        return &self.shapes;
+    }
+
+    pub fn get_opacity(&self) -> u8 {
+        return self.opacity;
+    }
+}
+
+impl Shape for Group {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_group(self);
+    }
+}
+
+impl std::fmt::Display for Group {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Group shapes: {} opacity: {}", self.shapes.len(), self.opacity);
+    }
+}
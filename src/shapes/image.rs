@@ -0,0 +1,55 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::point::Point;
+use std::string::String;
+
+use std::error::Error;
+
+// Largest width and height an image is drawn at, bounds the memory used to scale it
+pub const MAX_IMAGE_SIZE: u32 = 4096;
+
+// Bitmap loaded from a file, scaled to the given width and height. When only one of them is given the
+// other one keeps the aspect ratio of the file.
+pub struct Image {
+    path: String,
+    top_left: Point,
+    width: Option<u32>,
+    height: Option<u32>
+}
+
+impl Image {
+    pub fn new(path: String, top_left: Point, width: Option<u32>, height: Option<u32>) -> Self {
+        return Image{path: path, top_left: top_left, width: width, height: height};
+    }
+
+    pub fn get_path(&self) -> &String {
+        return &self.path;
+    }
+
+    pub fn get_top_left(&self) -> Point {
+        return self.top_left;
+    }
+
+    pub fn get_width(&self) -> Option<u32> {
+        return self.width;
+    }
+
+    pub fn get_height(&self) -> Option<u32> {
+        return self.height;
+    }
+}
+
+impl Shape for Image {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_image(self);
+    }
+}
+
+impl std::fmt::Display for Image {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size_to_string = |size: Option<u32>| size.map_or(String::from("original"), |size| size.to_string());
+        return write!(f, "Image path: {} top left point: {} width: {} height: {}",
+                      self.path, self.top_left, size_to_string(self.width), size_to_string(self.height));
+    }
+}
@@ -0,0 +1,38 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::layer::Layer;
+
+use std::error::Error;
+
+// Shape read from the input file together with the layer it belongs to
+pub struct LayeredShape {
+    shape: Box<dyn Shape>,
+    layer: Layer
+}
+
+impl LayeredShape {
+    pub fn new(shape: Box<dyn Shape>, layer: Layer) -> Self {
+        return LayeredShape{shape: shape, layer: layer};
+    }
+
+    pub fn get_shape(&self) -> &dyn Shape {
+        return self.shape.as_ref();
+    }
+}
+
+impl Shape for LayeredShape {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_layered_shape(self);
+    }
+
+    fn get_layer(&self) -> Option<&Layer> {
+        return Some(&self.layer);
+    }
+}
+
+impl std::fmt::Display for LayeredShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Layered shape {}", self.layer);
+    }
+}
@@ -1,19 +1,20 @@
 
 use crate::shapes::shape::Shape;
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
-use crate::utils::{point::Point, color::Color};
+use crate::utils::{point::Point, color::Color, stroke::Stroke};
 
 use std::error::Error;
 
 pub struct Line {
     p0: Point,
     p1: Point,
-    color: Color
+    color: Color,
+    stroke: Stroke
 }
 
 impl Line {
-    pub fn new(p0: Point, p1: Point, color: Color) -> Self {
-        return Line{p0: p0, p1: p1, color: color};
+    pub fn new(p0: Point, p1: Point, color: Color, stroke: Stroke) -> Self {
+        return Line{p0: p0, p1: p1, color: color, stroke: stroke};
     }
 
     pub fn get_p0(&self) -> Point {
@@ -27,17 +28,21 @@ impl Line {
     pub fn get_color(&self) -> Color {
         return self.color;
     }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
 }
 
 impl Shape for Line {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_line(&self);
+        return shape_visitor.visit_line(self);
     }
 }
 
 impl std::fmt::Display for Line {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(f, "Line p0: {} p1: {} color: {}",
-                      self.p0, self.p1, self.color);
+        return write!(f, "Line p0: {} p1: {} color: {} stroke {}",
+                      self.p0, self.p1, self.color, self.stroke);
     }
 }
@@ -0,0 +1,57 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{color::Color, paint::Paint, fill_rule::FillRule, path_command::PathCommand, stroke::Stroke};
+use std::vec::Vec;
+
+use std::error::Error;
+
+pub struct Path {
+    commands: Vec<PathCommand>,
+    outline_color: Color,
+    fill_paint: Paint,
+    fill_rule: FillRule,
+    stroke: Stroke
+}
+
+impl Path {
+    pub fn new(commands: Vec<PathCommand>, outline_color: Color, fill_paint: Paint, fill_rule: FillRule,
+               stroke: Stroke) -> Self {
+        return Path{commands: commands, outline_color: outline_color, fill_paint: fill_paint,
+                    fill_rule: fill_rule, stroke: stroke};
+    }
+
+    pub fn get_commands(&self) -> &Vec<PathCommand> {
+        return &self.commands;
+    }
+
+    pub fn get_outline_color(&self) -> Color {
+        return self.outline_color;
+    }
+
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_fill_rule(&self) -> FillRule {
+        return self.fill_rule;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
+}
+
+impl Shape for Path {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_path(self);
+    }
+}
+
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let commands: Vec<String> = self.commands.iter().map(|command| command.to_string()).collect();
+        return write!(f, "Path commands: [{}] outline color: {} fill paint: {} fill rule: {} stroke {}",
+                      commands.join(" "), self.outline_color, self.fill_paint, self.fill_rule, self.stroke);
+    }
+}
@@ -0,0 +1,83 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{point::Point, color::Color, paint::Paint, stroke::Stroke};
+
+use std::error::Error;
+
+// Slice of an ellipse bounded by an arc and the two radii going to the arc's ends
+pub struct Pie {
+    center: Point,
+    horizontal_radius: u32,
+    vertical_radius: u32,
+    // Clockwise, in degrees
+    start_angle: f64,
+    end_angle: f64,
+    rotation: f64,
+    outline_color: Color,
+    fill_paint: Paint,
+    stroke: Stroke
+}
+
+impl Pie {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(center: Point, horizontal_radius: u32, vertical_radius: u32,
+               start_angle: f64, end_angle: f64, rotation: f64,
+               outline_color: Color, fill_paint: Paint, stroke: Stroke)
+    -> Self {
+        return Pie{center: center, horizontal_radius: horizontal_radius, vertical_radius: vertical_radius,
+                   start_angle: start_angle, end_angle: end_angle, rotation: rotation,
+                   outline_color: outline_color, fill_paint: fill_paint, stroke: stroke};
+    }
+
+    pub fn get_center(&self) -> Point {
+        return self.center;
+    }
+
+    pub fn get_horizontal_radius(&self) -> u32 {
+        return self.horizontal_radius;
+    }
+
+    pub fn get_vertical_radius(&self) -> u32 {
+        return self.vertical_radius;
+    }
+
+    pub fn get_start_angle(&self) -> f64 {
+        return self.start_angle;
+    }
+
+    pub fn get_end_angle(&self) -> f64 {
+        return self.end_angle;
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        return self.rotation;
+    }
+
+    pub fn get_outline_color(&self) -> Color {
+        return self.outline_color;
+    }
+
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
+}
+
+impl Shape for Pie {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_pie(self);
+    }
+}
+
+impl std::fmt::Display for Pie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Pie center: {} horizontal radius: {} vertical radius: {} start angle: {} end angle: {} rotation: {} outline color: {} fill paint: {} stroke {}",
+                      self.center, self.horizontal_radius, self.vertical_radius,
+                      self.start_angle, self.end_angle, self.rotation,
+                      self.outline_color, self.fill_paint, self.stroke);
+    }
+}
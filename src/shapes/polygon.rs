@@ -1,7 +1,7 @@
 
 use crate::shapes::shape::Shape;
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
-use crate::utils::{point::Point, color::Color};
+use crate::utils::{point::Point, color::Color, paint::Paint, fill_rule::FillRule, stroke::Stroke};
 use std::vec::Vec;
 use std::string::String;
 
@@ -10,12 +10,16 @@ use std::error::Error;
 pub struct Polygon {
     points: Vec<Point>,
     outline_color: Color,
-    fill_color: Color
+    fill_paint: Paint,
+    fill_rule: FillRule,
+    stroke: Stroke
 }
 
 impl Polygon {
-    pub fn new(points: Vec<Point>, outline_color: Color, fill_color: Color) -> Self {
-        return Polygon{points: points, outline_color: outline_color, fill_color: fill_color};
+    pub fn new(points: Vec<Point>, outline_color: Color, fill_paint: Paint, fill_rule: FillRule,
+               stroke: Stroke) -> Self {
+        return Polygon{points: points, outline_color: outline_color, fill_paint: fill_paint,
+                       fill_rule: fill_rule, stroke: stroke};
     }
 
     pub fn get_points(&self) -> &Vec<Point> {
@@ -26,14 +30,22 @@ impl Polygon {
         return self.outline_color;
     }
 
-    pub fn get_fill_color(&self) -> Color {
-        return self.fill_color;
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_fill_rule(&self) -> FillRule {
+        return self.fill_rule;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
     }
 }
 
 impl Shape for Polygon {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_polygon(&self);
+        return shape_visitor.visit_polygon(self);
     }
 }
 
@@ -46,7 +58,8 @@ impl std::fmt::Display for Polygon {
             points_strings.push(point.to_string());
         }
 
-        return write!(f, "Polygon points: {} outline color: {} fill color: {}",
-                      points_strings.join(", "), self.outline_color, self.fill_color);
+        return write!(f, "Polygon points: {} outline color: {} fill paint: {} fill rule: {} stroke {}",
+                      points_strings.join(", "), self.outline_color, self.fill_paint, self.fill_rule,
+                      self.stroke);
     }
 }
@@ -0,0 +1,46 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{point::Point, color::Color, stroke::Stroke};
+use std::vec::Vec;
+use std::string::String;
+
+use std::error::Error;
+
+pub struct Polyline {
+    points: Vec<Point>,
+    color: Color,
+    stroke: Stroke
+}
+
+impl Polyline {
+    pub fn new(points: Vec<Point>, color: Color, stroke: Stroke) -> Self {
+        return Polyline{points: points, color: color, stroke: stroke};
+    }
+
+    pub fn get_points(&self) -> &Vec<Point> {
+        return &self.points;
+    }
+
+    pub fn get_color(&self) -> Color {
+        return self.color;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
+}
+
+impl Shape for Polyline {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_polyline(self);
+    }
+}
+
+impl std::fmt::Display for Polyline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let points_strings: Vec<String> = self.points.iter().map(|point| point.to_string()).collect();
+        return write!(f, "Polyline points: {} color: {} stroke {}",
+                      points_strings.join(", "), self.color, self.stroke);
+    }
+}
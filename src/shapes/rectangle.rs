@@ -1,7 +1,7 @@
 
 use crate::shapes::shape::Shape;
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
-use crate::utils::{point::Point, color::Color};
+use crate::utils::{point::Point, color::Color, paint::Paint, corner_radii::CornerRadii, stroke::Stroke};
 
 use std::error::Error;
 
@@ -10,14 +10,18 @@ pub struct Rectangle {
     height: u32,
     width: u32,
     outline_color: Color,
-    fill_color: Color
+    fill_paint: Paint,
+    corner_radii: CornerRadii,
+    stroke: Stroke
 }
 
 impl Rectangle {
-    pub fn new(top_left: Point, height: u32, width: u32, outline_color: Color, fill_color: Color)
+    pub fn new(top_left: Point, height: u32, width: u32, outline_color: Color, fill_paint: Paint,
+               corner_radii: CornerRadii, stroke: Stroke)
     -> Self {
         return Rectangle{top_left: top_left, height: height, width: width,
-                         outline_color: outline_color, fill_color: fill_color};
+                         outline_color: outline_color, fill_paint: fill_paint, corner_radii: corner_radii,
+                         stroke: stroke};
     }
 
     pub fn get_top_left(&self) -> Point {
@@ -36,21 +40,29 @@ impl Rectangle {
         return self.outline_color;
     }
 
-    pub fn get_fill_color(&self) -> Color {
-        return self.fill_color;
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_corner_radii(&self) -> CornerRadii {
+        return self.corner_radii;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
     }
 }
 
 impl Shape for Rectangle {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_rectangle(&self);
+        return shape_visitor.visit_rectangle(self);
     }
 }
 
 impl std::fmt::Display for Rectangle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(f, "Rectangle top left point: {} height: {} width: {} outline color: {} fill color: {}",
+        return write!(f, "Rectangle top left point: {} height: {} width: {} outline color: {} fill paint: {} corner radii: {} stroke {}",
                       self.top_left, self.height, self.width,
-                      self.outline_color, self.fill_color);
+                      self.outline_color, self.fill_paint, self.corner_radii, self.stroke);
     }
 }
@@ -0,0 +1,68 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{point::Point, color::Color, paint::Paint, stroke::Stroke};
+
+use std::error::Error;
+
+pub struct RegularPolygon {
+    center: Point,
+    radius: u32,
+    sides: u32,
+    // Clockwise, in degrees, the first vertex points up when zero
+    rotation: f64,
+    outline_color: Color,
+    fill_paint: Paint,
+    stroke: Stroke
+}
+
+impl RegularPolygon {
+    pub fn new(center: Point, radius: u32, sides: u32, rotation: f64,
+               outline_color: Color, fill_paint: Paint, stroke: Stroke)
+    -> Self {
+        return RegularPolygon{center: center, radius: radius, sides: sides, rotation: rotation,
+                              outline_color: outline_color, fill_paint: fill_paint, stroke: stroke};
+    }
+
+    pub fn get_center(&self) -> Point {
+        return self.center;
+    }
+
+    pub fn get_radius(&self) -> u32 {
+        return self.radius;
+    }
+
+    pub fn get_sides(&self) -> u32 {
+        return self.sides;
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        return self.rotation;
+    }
+
+    pub fn get_outline_color(&self) -> Color {
+        return self.outline_color;
+    }
+
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
+}
+
+impl Shape for RegularPolygon {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_regular_polygon(self);
+    }
+}
+
+impl std::fmt::Display for RegularPolygon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Regular polygon center: {} radius: {} sides: {} rotation: {} outline color: {} fill paint: {} stroke {}",
+                      self.center, self.radius, self.sides, self.rotation,
+                      self.outline_color, self.fill_paint, self.stroke);
+    }
+}
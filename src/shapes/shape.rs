@@ -1,8 +1,14 @@
 
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::layer::Layer;
 
 use std::error::Error;
 
 pub trait Shape {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>>;
+
+    // Layer of the input file the shape belongs to, none for the canvas and the shapes inside of blocks
+    fn get_layer(&self) -> Option<&Layer> {
+        return None;
+    }
 }
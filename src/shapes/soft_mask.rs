@@ -0,0 +1,56 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use std::vec::Vec;
+
+use std::error::Error;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum MaskMode {
+    // Opacity of the drawn shapes
+    Alpha,
+    // Brightness of the drawn shapes, made transparent by their opacity
+    Luminance
+}
+
+// Shapes drawn offscreen whose alpha or luminance modulates the opacity of the shapes that follow them,
+// up to the end of the enclosing group or file. Soft masks combine with each other and with clipping regions.
+pub struct SoftMask {
+    shapes: Vec<Box<dyn Shape>>,
+    mode: MaskMode
+}
+
+impl SoftMask {
+    pub fn new(shapes: Vec<Box<dyn Shape>>, mode: MaskMode) -> Self {
+        return SoftMask{shapes: shapes, mode: mode};
+    }
+
+    pub fn get_shapes(&self) -> &Vec<Box<dyn Shape>> {
+        return &self.shapes;
+    }
+
+    pub fn get_mode(&self) -> MaskMode {
+        return self.mode;
+    }
+}
+
+impl Shape for SoftMask {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_soft_mask(self);
+    }
+}
+
+impl std::fmt::Display for MaskMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            MaskMode::Alpha => write!(f, "alpha"),
+            MaskMode::Luminance => write!(f, "luminance")
+        };
+    }
+}
+
+impl std::fmt::Display for SoftMask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Soft mask shapes: {} mode: {}", self.shapes.len(), self.mode);
+    }
+}
@@ -1,7 +1,7 @@
 
 use crate::shapes::shape::Shape;
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
-use crate::utils::{point::Point, color::Color};
+use crate::utils::{point::Point, color::Color, paint::Paint, corner_radii::CornerRadii, stroke::Stroke};
 
 use std::error::Error;
 
@@ -9,15 +9,18 @@ pub struct Square {
     top_left: Point,
     side: u32,
     outline_color: Color,
-    fill_color: Color
+    fill_paint: Paint,
+    corner_radii: CornerRadii,
+    stroke: Stroke
 }
 
 impl Square {
     pub fn new(top_left: Point, side: u32,
-           outline_color: Color, fill_color: Color)
+           outline_color: Color, fill_paint: Paint, corner_radii: CornerRadii, stroke: Stroke)
         -> Self {
         return Square{top_left: top_left, side: side,
-                      outline_color: outline_color, fill_color: fill_color};
+                      outline_color: outline_color, fill_paint: fill_paint, corner_radii: corner_radii,
+                      stroke: stroke};
     }
 
     pub fn get_top_left(&self) -> Point {
@@ -32,21 +35,29 @@ impl Square {
         return self.outline_color;
     }
 
-    pub fn get_fill_color(&self) -> Color {
-        return self.fill_color;
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_corner_radii(&self) -> CornerRadii {
+        return self.corner_radii;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
     }
 }
 
 impl Shape for Square {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_square(&self);
+        return shape_visitor.visit_square(self);
     }
 }
 
 impl std::fmt::Display for Square {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(f, "Square top left point: {} side: {} outline color: {} fill color: {}",
+        return write!(f, "Square top left point: {} side: {} outline color: {} fill paint: {} corner radii: {} stroke {}",
                       self.top_left, self.side,
-                      self.outline_color, self.fill_color);
+                      self.outline_color, self.fill_paint, self.corner_radii, self.stroke);
     }
 }
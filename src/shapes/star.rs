@@ -0,0 +1,74 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{point::Point, color::Color, paint::Paint, stroke::Stroke};
+
+use std::error::Error;
+
+pub struct Star {
+    center: Point,
+    outer_radius: u32,
+    inner_radius: u32,
+    points: u32,
+    // Clockwise, in degrees, the first point points up when zero
+    rotation: f64,
+    outline_color: Color,
+    fill_paint: Paint,
+    stroke: Stroke
+}
+
+impl Star {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(center: Point, outer_radius: u32, inner_radius: u32, points: u32, rotation: f64,
+               outline_color: Color, fill_paint: Paint, stroke: Stroke)
+    -> Self {
+        return Star{center: center, outer_radius: outer_radius, inner_radius: inner_radius, points: points,
+                    rotation: rotation, outline_color: outline_color, fill_paint: fill_paint, stroke: stroke};
+    }
+
+    pub fn get_center(&self) -> Point {
+        return self.center;
+    }
+
+    pub fn get_outer_radius(&self) -> u32 {
+        return self.outer_radius;
+    }
+
+    pub fn get_inner_radius(&self) -> u32 {
+        return self.inner_radius;
+    }
+
+    pub fn get_points(&self) -> u32 {
+        return self.points;
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        return self.rotation;
+    }
+
+    pub fn get_outline_color(&self) -> Color {
+        return self.outline_color;
+    }
+
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
+    }
+}
+
+impl Shape for Star {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_star(self);
+    }
+}
+
+impl std::fmt::Display for Star {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Star center: {} outer radius: {} inner radius: {} points: {} rotation: {} outline color: {} fill paint: {} stroke {}",
+                      self.center, self.outer_radius, self.inner_radius, self.points, self.rotation,
+                      self.outline_color, self.fill_paint, self.stroke);
+    }
+}
@@ -0,0 +1,72 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::{point::Point, color::Color, text_alignment::{TextAlignment, VerticalAlignment}};
+use std::string::String;
+
+use std::error::Error;
+
+pub struct Text {
+    // Aligned with the lines of the text as given by the alignments
+    position: Point,
+    // Height of the glyphs of the bitmap font or of the em square of a font file, in pixels
+    size: u32,
+    color: Color,
+    // Lines are separated by newlines
+    content: String,
+    alignment: TextAlignment,
+    vertical_alignment: VerticalAlignment,
+    // TrueType or OpenType file, the built-in bitmap font is used when missing
+    font: Option<String>
+}
+
+impl Text {
+    pub fn new(position: Point, size: u32, color: Color, content: String, alignment: TextAlignment,
+               vertical_alignment: VerticalAlignment, font: Option<String>) -> Self {
+        return Text{position: position, size: size, color: color, content: content, alignment: alignment,
+                    vertical_alignment: vertical_alignment, font: font};
+    }
+
+    pub fn get_position(&self) -> Point {
+        return self.position;
+    }
+
+    pub fn get_size(&self) -> u32 {
+        return self.size;
+    }
+
+    pub fn get_color(&self) -> Color {
+        return self.color;
+    }
+
+    pub fn get_content(&self) -> &String {
+        return &self.content;
+    }
+
+    pub fn get_alignment(&self) -> TextAlignment {
+        return self.alignment;
+    }
+
+    pub fn get_vertical_alignment(&self) -> VerticalAlignment {
+        return self.vertical_alignment;
+    }
+
+    pub fn get_font(&self) -> Option<&String> {
+        return self.font.as_ref();
+    }
+}
+
+impl Shape for Text {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_text(self);
+    }
+}
+
+impl std::fmt::Display for Text {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let font = self.font.as_deref().unwrap_or("built-in");
+        return write!(f, "Text position: {} size: {} color: {} content: \"{}\" alignment: {} vertical alignment: {} font: {}",
+                      self.position, self.size, self.color, self.content.escape_debug(), self.alignment,
+                      self.vertical_alignment, font);
+    }
+}
@@ -0,0 +1,38 @@
+
+use crate::shapes::shape::Shape;
+use crate::shape_visitor::shape_visitor::ShapeVisitor;
+use crate::utils::transform::Transform;
+
+use std::error::Error;
+
+// Shape drawn with an affine transform applied to its geometry
+pub struct TransformedShape {
+    shape: Box<dyn Shape>,
+    transform: Transform
+}
+
+impl TransformedShape {
+    pub fn new(shape: Box<dyn Shape>, transform: Transform) -> Self {
+        return TransformedShape{shape: shape, transform: transform};
+    }
+
+    pub fn get_shape(&self) -> &dyn Shape {
+        return self.shape.as_ref();
+    }
+
+    pub fn get_transform(&self) -> Transform {
+        return self.transform;
+    }
+}
+
+impl Shape for TransformedShape {
+    fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
+        return shape_visitor.visit_transformed_shape(self);
+    }
+}
+
+impl std::fmt::Display for TransformedShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Transformed shape transform: {}", self.transform);
+    }
+}
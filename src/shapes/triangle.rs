@@ -1,7 +1,7 @@
 
 use crate::shapes::shape::Shape;
 use crate::shape_visitor::shape_visitor::ShapeVisitor;
-use crate::utils::{point::Point, color::Color};
+use crate::utils::{point::Point, color::Color, paint::Paint, stroke::Stroke};
 
 use std::error::Error;
 
@@ -10,14 +10,15 @@ pub struct Triangle {
     p1: Point,
     p2: Point,
     outline_color: Color,
-    fill_color: Color
+    fill_paint: Paint,
+    stroke: Stroke
 }
 
 impl Triangle {
-    pub fn new(p0: Point, p1: Point, p2: Point, outline_color: Color, fill_color: Color)
+    pub fn new(p0: Point, p1: Point, p2: Point, outline_color: Color, fill_paint: Paint, stroke: Stroke)
     -> Self {
         return Triangle{p0: p0, p1: p1, p2: p2,
-                        outline_color: outline_color, fill_color: fill_color};
+                        outline_color: outline_color, fill_paint: fill_paint, stroke: stroke};
     }
 
     pub fn get_p0(&self) -> Point {
@@ -36,21 +37,25 @@ impl Triangle {
         return self.outline_color;
     }
 
-    pub fn get_fill_color(&self) -> Color {
-        return self.fill_color;
+    pub fn get_fill_paint(&self) -> &Paint {
+        return &self.fill_paint;
+    }
+
+    pub fn get_stroke(&self) -> &Stroke {
+        return &self.stroke;
     }
 }
 
 impl Shape for Triangle {
     fn accept(&self, shape_visitor: &mut dyn ShapeVisitor) -> Result<(), Box<dyn Error>> {
-        return shape_visitor.visit_triangle(&self);
+        return shape_visitor.visit_triangle(self);
     }
 }
 
 impl std::fmt::Display for Triangle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(f, "Triangle p0: {} p1: {} p2: {} outline color: {} fill color: {}",
+        return write!(f, "Triangle p0: {} p1: {} p2: {} outline color: {} fill paint: {} stroke {}",
                       self.p0, self.p1, self.p2,
-                      self.outline_color, self.fill_color);
+                      self.outline_color, self.fill_paint, self.stroke);
     }
 }
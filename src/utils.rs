@@ -2,3 +2,14 @@
 pub mod point;
 pub mod color;
 pub mod generic_error;
+pub mod fill_rule;
+pub mod float_point;
+pub mod stroke;
+pub mod path_command;
+pub mod corner_radii;
+pub mod paint;
+pub mod text_alignment;
+pub mod transform;
+pub mod blend_mode;
+pub mod effect;
+pub mod layer;
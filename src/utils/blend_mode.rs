@@ -0,0 +1,58 @@
+
+// How the colors of a shape are combined with the colors already drawn below it
+// https://www.w3.org/TR/compositing-1/
+#[derive(Copy, Clone, PartialEq, Default)]
+pub enum BlendMode {
+    // Separable blend modes, the blended color is then composited source-over
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    Difference,
+    // Porter-Duff operators
+    Clear,
+    Copy,
+    DestinationOver,
+    SourceIn,
+    DestinationIn,
+    SourceOut,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Xor
+}
+
+impl BlendMode {
+    pub fn is_porter_duff(&self) -> bool {
+        return !matches!(self, BlendMode::Normal | BlendMode::Multiply | BlendMode::Screen | BlendMode::Overlay
+                               | BlendMode::Darken | BlendMode::Lighten | BlendMode::Difference);
+    }
+}
+
+impl std::fmt::Display for BlendMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Overlay => "overlay",
+            BlendMode::Darken => "darken",
+            BlendMode::Lighten => "lighten",
+            BlendMode::Difference => "difference",
+            BlendMode::Clear => "clear",
+            BlendMode::Copy => "copy",
+            BlendMode::DestinationOver => "destination-over",
+            BlendMode::SourceIn => "source-in",
+            BlendMode::DestinationIn => "destination-in",
+            BlendMode::SourceOut => "source-out",
+            BlendMode::DestinationOut => "destination-out",
+            BlendMode::SourceAtop => "source-atop",
+            BlendMode::DestinationAtop => "destination-atop",
+            BlendMode::Xor => "xor"
+        };
+        return write!(f, "{}", name);
+    }
+}
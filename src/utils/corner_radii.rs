@@ -0,0 +1,31 @@
+
+// Radii of the rounded corners of a rectangle, zero for a sharp corner
+#[derive(Copy, Clone, Default)]
+pub struct CornerRadii {
+    pub top_left: u32,
+    pub top_right: u32,
+    pub bottom_right: u32,
+    pub bottom_left: u32
+}
+
+impl CornerRadii {
+    pub fn new(top_left: u32, top_right: u32, bottom_right: u32, bottom_left: u32) -> Self {
+        return CornerRadii{top_left: top_left, top_right: top_right,
+                           bottom_right: bottom_right, bottom_left: bottom_left};
+    }
+
+    pub fn uniform(radius: u32) -> Self {
+        return CornerRadii::new(radius, radius, radius, radius);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.top_left == 0 && self.top_right == 0 && self.bottom_right == 0 && self.bottom_left == 0;
+    }
+}
+
+impl std::fmt::Display for CornerRadii {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "top left: {} top right: {} bottom right: {} bottom left: {}",
+                      self.top_left, self.top_right, self.bottom_right, self.bottom_left);
+    }
+}
@@ -0,0 +1,31 @@
+
+use crate::utils::{ color::Color, point::Point };
+
+// Larger blurs spread a shape too thin to be seen and take too long to compute
+pub const MAX_BLUR_RADIUS: u32 = 250;
+
+// Filter drawn from the pixels of a shape once the shape is drawn offscreen. Blur radii are in pixels of the
+// image, the standard deviation of the Gaussian blur being half of the radius.
+#[derive(Copy, Clone)]
+pub enum Effect {
+    // Blurred silhouette of the shape drawn below it, moved by the offset
+    DropShadow { offset: Point, blur_radius: u32, color: Color },
+    // Blurred silhouette of the shape drawn around it, hidden where the shape is drawn
+    OuterGlow { blur_radius: u32, color: Color },
+    // Blur of the shape itself
+    Blur { radius: u32 }
+}
+
+impl std::fmt::Display for Effect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Effect::DropShadow { offset, blur_radius, color } => {
+                write!(f, "drop shadow offset: {} blur radius: {} color: {}", offset, blur_radius, color)
+            },
+            Effect::OuterGlow { blur_radius, color } => {
+                write!(f, "outer glow blur radius: {} color: {}", blur_radius, color)
+            },
+            Effect::Blur { radius } => write!(f, "blur radius: {}", radius)
+        };
+    }
+}
@@ -0,0 +1,15 @@
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero
+}
+
+impl std::fmt::Display for FillRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            FillRule::EvenOdd => write!(f, "even-odd"),
+            FillRule::NonZero => write!(f, "non-zero")
+        };
+    }
+}
@@ -0,0 +1,56 @@
+
+use crate::utils::point::Point;
+
+// Point with sub-pixel precision, used for geometry computed while rasterizing
+#[derive(Copy, Clone)]
+pub struct FloatPoint {
+    pub x: f64,
+    pub y: f64
+}
+
+impl FloatPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        return FloatPoint{x: x, y: y};
+    }
+
+    pub fn add(&self, other: FloatPoint) -> FloatPoint {
+        return FloatPoint::new(self.x + other.x, self.y + other.y);
+    }
+
+    pub fn subtract(&self, other: FloatPoint) -> FloatPoint {
+        return FloatPoint::new(self.x - other.x, self.y - other.y);
+    }
+
+    pub fn scale(&self, factor: f64) -> FloatPoint {
+        return FloatPoint::new(self.x * factor, self.y * factor);
+    }
+
+    pub fn length(&self) -> f64 {
+        return self.x.hypot(self.y);
+    }
+
+    pub fn round(&self) -> Point {
+        return Point::new(self.x.round() as i32, self.y.round() as i32);
+    }
+
+    // Unit vector rotated 90 degrees clockwise on screen, None for the null vector
+    pub fn normal(&self) -> Option<FloatPoint> {
+        let length = self.length();
+        if length == 0.0 {
+            return None;
+        }
+        return Some(FloatPoint::new(-self.y / length, self.x / length));
+    }
+}
+
+impl From<Point> for FloatPoint {
+    fn from(point: Point) -> Self {
+        return FloatPoint::new(point.x as f64, point.y as f64);
+    }
+}
+
+impl std::fmt::Display for FloatPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "({}, {})", self.x, self.y);
+    }
+}
@@ -0,0 +1,41 @@
+
+use std::string::String;
+
+// Named section of the input file. A layer is hidden or locked when any of its LAYER lines says so.
+#[derive(Clone)]
+pub struct Layer {
+    name: String,
+    is_hidden: bool,
+    // Locked layers keep the visibility set in the input file, command line options cannot change it
+    is_locked: bool
+}
+
+impl Layer {
+    pub fn new(name: String, is_hidden: bool, is_locked: bool) -> Self {
+        return Layer{name: name, is_hidden: is_hidden, is_locked: is_locked};
+    }
+
+    pub fn get_name(&self) -> &String {
+        return &self.name;
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        return self.is_hidden;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        return self.is_locked;
+    }
+
+    // Adds the flags of another LAYER line of the same layer
+    pub fn merge(&mut self, other: &Layer) {
+        self.is_hidden |= other.is_hidden;
+        self.is_locked |= other.is_locked;
+    }
+}
+
+impl std::fmt::Display for Layer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Layer name: {} hidden: {} locked: {}", self.name, self.is_hidden, self.is_locked);
+    }
+}
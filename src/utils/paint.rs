@@ -0,0 +1,83 @@
+
+use crate::utils::{ color::Color, float_point::FloatPoint };
+use std::vec::Vec;
+use std::string::String;
+
+// Color of a gradient at a position between 0 (start) and 1 (end) of the gradient
+#[derive(Copy, Clone)]
+pub struct ColorStop {
+    pub offset: f64,
+    pub color: Color
+}
+
+impl ColorStop {
+    pub fn new(offset: f64, color: Color) -> Self {
+        return ColorStop{offset: offset, color: color};
+    }
+}
+
+// Repeated motif of a pattern paint
+#[derive(Copy, Clone, PartialEq)]
+pub enum Pattern {
+    // Parallel lines
+    Hatch,
+    // Two sets of parallel lines crossing at right angles
+    CrossHatch,
+    // Dots on a square grid, the line width is their diameter
+    Dots
+}
+
+// How the inside of a shape is colored, evaluated for every pixel. Gradients keep the color of their
+// first and last stops beyond their ends.
+#[derive(Clone)]
+pub enum Paint {
+    Solid(Color),
+    // Stops go from the start point to the end point, along the line between them
+    LinearGradient { start: FloatPoint, end: FloatPoint, stops: Vec<ColorStop> },
+    // Stops go from the center to the circle of the given radius
+    RadialGradient { center: FloatPoint, radius: f64, stops: Vec<ColorStop> },
+    // Foreground motif drawn over a background color, anchored to the canvas origin so that the patterns
+    // of adjacent shapes line up. The angle is clockwise, in degrees, and the spacing is the distance
+    // between neighbouring lines or dots.
+    Pattern { pattern: Pattern, angle: f64, spacing: f64, line_width: f64, foreground: Color, background: Color },
+    // Image file repeated in both directions, one of its copies has its top left corner at the origin
+    Texture { path: String, origin: FloatPoint }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Pattern::Hatch => write!(f, "hatch"),
+            Pattern::CrossHatch => write!(f, "cross hatch"),
+            Pattern::Dots => write!(f, "dots")
+        };
+    }
+}
+
+impl std::fmt::Display for ColorStop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} at {}", self.color, self.offset);
+    }
+}
+
+impl std::fmt::Display for Paint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Paint::Solid(color) => write!(f, "{}", color),
+            Paint::LinearGradient { start, end, stops } => {
+                let stops_strings: Vec<String> = stops.iter().map(|stop| stop.to_string()).collect();
+                write!(f, "linear gradient from {} to {} stops: [{}]", start, end, stops_strings.join(", "))
+            },
+            Paint::RadialGradient { center, radius, stops } => {
+                let stops_strings: Vec<String> = stops.iter().map(|stop| stop.to_string()).collect();
+                write!(f, "radial gradient center: {} radius: {} stops: [{}]", center, radius,
+                       stops_strings.join(", "))
+            },
+            Paint::Pattern { pattern, angle, spacing, line_width, foreground, background } => {
+                write!(f, "{} pattern angle: {} spacing: {} line width: {} foreground: {} background: {}",
+                       pattern, angle, spacing, line_width, foreground, background)
+            },
+            Paint::Texture { path, origin } => write!(f, "texture {} origin: {}", path, origin)
+        };
+    }
+}
@@ -0,0 +1,26 @@
+
+use crate::utils::float_point::FloatPoint;
+
+// Drawing commands of a path, modeled on SVG path data with absolute coordinates
+#[derive(Copy, Clone)]
+pub enum PathCommand {
+    MoveTo(FloatPoint),
+    LineTo(FloatPoint),
+    // Control point and end point
+    QuadraticTo(FloatPoint, FloatPoint),
+    // Two control points and end point
+    CubicTo(FloatPoint, FloatPoint, FloatPoint),
+    Close
+}
+
+impl std::fmt::Display for PathCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            PathCommand::MoveTo(point) => write!(f, "M {}", point),
+            PathCommand::LineTo(point) => write!(f, "L {}", point),
+            PathCommand::QuadraticTo(control, end) => write!(f, "Q {} {}", control, end),
+            PathCommand::CubicTo(control0, control1, end) => write!(f, "C {} {} {}", control0, control1, end),
+            PathCommand::Close => write!(f, "Z")
+        };
+    }
+}
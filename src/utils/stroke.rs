@@ -0,0 +1,127 @@
+
+use std::vec::Vec;
+use std::string::String;
+
+// Shape drawn at the ends of open outlines
+#[derive(Copy, Clone, PartialEq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square
+}
+
+// Shape drawn where two segments of an outline meet
+#[derive(Copy, Clone, PartialEq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel
+}
+
+// How the outline of a shape is drawn
+#[derive(Clone)]
+pub struct Stroke {
+    width: u32,
+    cap: LineCap,
+    join: LineJoin,
+    // Miter joins longer than miter_limit * width are drawn as bevel joins
+    miter_limit: f64,
+    // Alternating lengths of dashes and gaps, the outline is solid if there are none
+    dash_array: Vec<f64>,
+    dash_offset: f64
+}
+
+impl Stroke {
+    pub fn new(width: u32, cap: LineCap, join: LineJoin, miter_limit: f64,
+               dash_array: Vec<f64>, dash_offset: f64) -> Self {
+        return Stroke{width: width, cap: cap, join: join, miter_limit: miter_limit,
+                      dash_array: dash_array, dash_offset: dash_offset};
+    }
+
+    pub fn get_width(&self) -> u32 {
+        return self.width;
+    }
+
+    pub fn get_cap(&self) -> LineCap {
+        return self.cap;
+    }
+
+    pub fn get_join(&self) -> LineJoin {
+        return self.join;
+    }
+
+    pub fn get_miter_limit(&self) -> f64 {
+        return self.miter_limit;
+    }
+
+    pub fn get_dash_array(&self) -> &Vec<f64> {
+        return &self.dash_array;
+    }
+
+    pub fn get_dash_offset(&self) -> f64 {
+        return self.dash_offset;
+    }
+
+    pub fn set_width(&mut self, width: u32) {
+        self.width = width;
+    }
+
+    pub fn set_cap(&mut self, cap: LineCap) {
+        self.cap = cap;
+    }
+
+    pub fn set_join(&mut self, join: LineJoin) {
+        self.join = join;
+    }
+
+    pub fn set_miter_limit(&mut self, miter_limit: f64) {
+        self.miter_limit = miter_limit;
+    }
+
+    pub fn set_dash_array(&mut self, dash_array: Vec<f64>) {
+        self.dash_array = dash_array;
+    }
+
+    pub fn set_dash_offset(&mut self, dash_offset: f64) {
+        self.dash_offset = dash_offset;
+    }
+
+    pub fn is_dashed(&self) -> bool {
+        return !self.dash_array.is_empty();
+    }
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        return Stroke::new(1, LineCap::Butt, LineJoin::Miter, 4.0, Vec::new(), 0.0);
+    }
+}
+
+impl std::fmt::Display for LineCap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            LineCap::Butt => write!(f, "butt"),
+            LineCap::Round => write!(f, "round"),
+            LineCap::Square => write!(f, "square")
+        };
+    }
+}
+
+impl std::fmt::Display for LineJoin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            LineJoin::Miter => write!(f, "miter"),
+            LineJoin::Round => write!(f, "round"),
+            LineJoin::Bevel => write!(f, "bevel")
+        };
+    }
+}
+
+impl std::fmt::Display for Stroke {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dash_strings: Vec<String> = self.dash_array.iter().map(|length| length.to_string()).collect();
+        return write!(f, "width: {} cap: {} join: {} miter limit: {} dash array: [{}] dash offset: {}",
+                      self.width, self.cap, self.join, self.miter_limit,
+                      dash_strings.join(", "), self.dash_offset);
+    }
+}
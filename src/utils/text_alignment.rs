@@ -0,0 +1,42 @@
+
+// Which point of every line of a text is placed on the position of the text
+#[derive(Copy, Clone, PartialEq)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right
+}
+
+// Which line of a text is placed on the vertical position of the text
+#[derive(Copy, Clone, PartialEq)]
+pub enum VerticalAlignment {
+    // Top of the first line
+    Top,
+    // Middle between the top of the first line and the bottom of the last one
+    Middle,
+    // Baseline of the first line
+    Baseline,
+    // Bottom of the last line, including the parts of the glyphs below the baseline
+    Bottom
+}
+
+impl std::fmt::Display for TextAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            TextAlignment::Left => write!(f, "left"),
+            TextAlignment::Center => write!(f, "center"),
+            TextAlignment::Right => write!(f, "right")
+        };
+    }
+}
+
+impl std::fmt::Display for VerticalAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            VerticalAlignment::Top => write!(f, "top"),
+            VerticalAlignment::Middle => write!(f, "middle"),
+            VerticalAlignment::Baseline => write!(f, "baseline"),
+            VerticalAlignment::Bottom => write!(f, "bottom")
+        };
+    }
+}
@@ -0,0 +1,104 @@
+
+use crate::utils::float_point::FloatPoint;
+
+// Affine transform mapping (x, y) to (a * x + c * y + e, b * x + d * y + f)
+#[derive(Copy, Clone, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64
+}
+
+impl Transform {
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        return Transform{a: a, b: b, c: c, d: d, e: e, f: f};
+    }
+
+    pub fn identity() -> Self {
+        return Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+    }
+
+    pub fn translate(tx: f64, ty: f64) -> Self {
+        return Transform::new(1.0, 0.0, 0.0, 1.0, tx, ty);
+    }
+
+    // Angle in degrees, clockwise on screen around the given center
+    pub fn rotate(angle: f64, center: FloatPoint) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let rotation = Transform::new(cos, sin, -sin, cos, 0.0, 0.0);
+        return Transform::translate(-center.x, -center.y)
+            .then(&rotation)
+            .then(&Transform::translate(center.x, center.y));
+    }
+
+    pub fn scale(sx: f64, sy: f64, center: FloatPoint) -> Self {
+        return Transform::translate(-center.x, -center.y)
+            .then(&Transform::new(sx, 0.0, 0.0, sy, 0.0, 0.0))
+            .then(&Transform::translate(center.x, center.y));
+    }
+
+    // Angles in degrees along the x and y axes
+    pub fn skew(angle_x: f64, angle_y: f64) -> Self {
+        return Transform::new(1.0, angle_y.to_radians().tan(), angle_x.to_radians().tan(), 1.0, 0.0, 0.0);
+    }
+
+    pub fn is_identity(&self) -> bool {
+        return *self == Transform::identity();
+    }
+
+    // Transform applying this one first and the other one afterwards
+    pub fn then(&self, other: &Transform) -> Transform {
+        return Transform::new(other.a * self.a + other.c * self.b,
+                              other.b * self.a + other.d * self.b,
+                              other.a * self.c + other.c * self.d,
+                              other.b * self.c + other.d * self.d,
+                              other.a * self.e + other.c * self.f + other.e,
+                              other.b * self.e + other.d * self.f + other.f);
+    }
+
+    // Largest factor by which distances are stretched, the largest singular value of the linear part
+    pub fn get_scale_factor(&self) -> f64 {
+        let squares_sum = self.a * self.a + self.b * self.b + self.c * self.c + self.d * self.d;
+        let determinant = self.a * self.d - self.b * self.c;
+        let discriminant = (squares_sum * squares_sum - 4.0 * determinant * determinant).max(0.0).sqrt();
+        return ((squares_sum + discriminant) / 2.0).sqrt();
+    }
+
+    // Transforms with infinite coefficients or flattening shapes to a line or a point cannot be drawn
+    pub fn is_invertible(&self) -> bool {
+        let coefficients = [self.a, self.b, self.c, self.d, self.e, self.f];
+        return coefficients.iter().all(|coefficient| coefficient.is_finite()) && self.inverse().is_some();
+    }
+
+    pub fn inverse(&self) -> Option<Transform> {
+        let determinant = self.a * self.d - self.b * self.c;
+        if determinant.is_nan() || determinant.abs() < 1e-12 {
+            return None;
+        }
+        let a = self.d / determinant;
+        let b = -self.b / determinant;
+        let c = -self.c / determinant;
+        let d = self.a / determinant;
+        return Some(Transform::new(a, b, c, d, -(a * self.e + c * self.f), -(b * self.e + d * self.f)));
+    }
+
+    pub fn apply(&self, point: FloatPoint) -> FloatPoint {
+        return FloatPoint::new(self.a * point.x + self.c * point.y + self.e,
+                               self.b * point.x + self.d * point.y + self.f);
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        return Transform::identity();
+    }
+}
+
+impl std::fmt::Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "matrix({}, {}, {}, {}, {}, {})", self.a, self.b, self.c, self.d, self.e, self.f);
+    }
+}